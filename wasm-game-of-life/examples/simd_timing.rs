@@ -0,0 +1,28 @@
+// Times `tick` on the SIMD and the scalar backend with `set_perf`.
+//
+// Run with `cargo run --release --example simd_timing --features simd`. For
+// the `v128` backend, build the web page with
+// `RUSTFLAGS="-C target-feature=+simd128"` and compare the `Universe::tick`
+// timers in the console with and without `?scalar` in the URL.
+
+use wasm_game_of_life::{InitType, Universe};
+
+const SIZE: u32 = 2048;
+const TICKS: usize = 5;
+
+fn main() {
+    for &simd in &[false, true] {
+        let mut universe = Universe::with_size(SIZE, SIZE, InitType::Clear).unwrap();
+        universe.set_seed(1);
+        universe.put_random();
+        universe.set_simd(simd);
+        // The first tick sets up the buffers, so it is not timed.
+        universe.tick();
+
+        println!("{}:", if simd { "simd" } else { "scalar" });
+        universe.set_perf();
+        for _ in 0..TICKS {
+            universe.tick();
+        }
+    }
+}
@@ -0,0 +1,47 @@
+// A global allocator that counts allocations made on the current thread, for
+// checking that hot paths like `Universe::tick` do not allocate.
+
+use std::alloc::{GlobalAlloc, Layout};
+use std::cell::Cell;
+
+#[cfg(feature = "wee_alloc")]
+type Inner = wee_alloc::WeeAlloc<'static>;
+#[cfg(not(feature = "wee_alloc"))]
+type Inner = std::alloc::System;
+
+thread_local! {
+    static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+fn count() {
+    // The counter may already be gone while a thread shuts down.
+    let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+}
+
+/// Allocations made so far on the current thread.
+pub(crate) fn allocations() -> u64 {
+    ALLOCATIONS.with(Cell::get)
+}
+
+pub(crate) struct Counting(pub(crate) Inner);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count();
+        self.0.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count();
+        self.0.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count();
+        self.0.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.0.dealloc(ptr, layout)
+    }
+}
@@ -0,0 +1,187 @@
+use crate::census::Classification;
+use crate::error::ParseError;
+use crate::pattern::{Pattern, Transform};
+use std::fmt;
+use std::str::FromStr;
+
+// Digits of a 5-cell column, bit 0 being the top cell. Runs of empty columns
+// use `w`, `x` and `y`, so only `0` to `v` stand for columns.
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// What the prefix of an apgcode says about the object.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ApgcodeKind {
+    /// `xs`, followed by the population.
+    StillLife { population: u32 },
+    /// `xp`, followed by the period.
+    Oscillator { period: u32 },
+    /// `xq`, followed by the period.
+    Spaceship { period: u32 },
+}
+
+/// An object in the apgcode format used by Catagolue, such as `xs4_33` for
+/// the block or `xq4_153` for the glider. The part after the underscore is
+/// the extended Wechsler format: the pattern is cut into strips 5 rows high,
+/// separated by `z`, and each column of a strip is written as one digit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Apgcode {
+    pub kind: ApgcodeKind,
+    /// Live cells as `(col, row)` pairs.
+    pub cells: Vec<(u32, u32)>,
+}
+
+fn push_empty_columns(out: &mut String, mut n: usize) {
+    while n > 0 {
+        let run = n.min(39);
+        match run {
+            1 => out.push('0'),
+            2 => out.push('w'),
+            3 => out.push('x'),
+            _ => {
+                out.push('y');
+                out.push(DIGITS[run - 4] as char);
+            }
+        }
+        n -= run;
+    }
+}
+
+/// Writes `cells` in extended Wechsler format.
+pub(crate) fn wechsler(cells: &[(u32, u32)]) -> String {
+    let width = cells.iter().map(|&(col, _)| col as usize + 1).max().unwrap_or(0);
+    let height = cells.iter().map(|&(_, row)| row as usize + 1).max().unwrap_or(0);
+    let mut columns = vec![0u8; width * height.div_ceil(5)];
+    for &(col, row) in cells {
+        columns[row as usize / 5 * width + col as usize] |= 1 << (row % 5);
+    }
+
+    let mut out = String::new();
+    for (i, strip) in columns.chunks(width.max(1)).enumerate() {
+        if i > 0 {
+            out.push('z');
+        }
+        // Empty columns at the end of a strip are left out.
+        let mut empty = 0;
+        for &column in strip {
+            if column == 0 {
+                empty += 1;
+                continue;
+            }
+            push_empty_columns(&mut out, empty);
+            empty = 0;
+            out.push(DIGITS[column as usize] as char);
+        }
+    }
+    out
+}
+
+/// The smallest orientation of any of `phases`: the one with the shortest
+/// extended Wechsler code, and the first in ASCII order among those.
+pub(crate) fn canonical_form(phases: &[Pattern]) -> (Vec<(u32, u32)>, String) {
+    phases
+        .iter()
+        .flat_map(|phase| Transform::ALL.iter().map(move |&t| phase.transformed(t)))
+        .map(|pattern| {
+            let code = wechsler(pattern.cells());
+            (pattern.cells().to_vec(), code)
+        })
+        .min_by(|(_, a), (_, b)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+        .unwrap_or_default()
+}
+
+impl Apgcode {
+    /// Names an object from what it does and every phase of its cycle, as
+    /// returned by `census::classify`. Objects that are not periodic have
+    /// no apgcode.
+    pub(crate) fn canonical(
+        classification: Classification,
+        phases: &[Pattern],
+    ) -> Option<Apgcode> {
+        let (cells, _) = canonical_form(phases);
+        let kind = match classification {
+            Classification::StillLife => ApgcodeKind::StillLife {
+                population: cells.len() as u32,
+            },
+            Classification::Oscillator { period } => ApgcodeKind::Oscillator { period },
+            Classification::Spaceship { period, .. } => ApgcodeKind::Spaceship { period },
+            Classification::Unknown => return None,
+        };
+        Some(Apgcode { kind, cells })
+    }
+}
+
+impl FromStr for Apgcode {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let error = |byte: usize, message: String| ParseError::new(1, byte + 1, message);
+        let underscore = input
+            .find('_')
+            .ok_or_else(|| error(input.len(), "expected `_` after the prefix".to_string()))?;
+        let prefix = &input[..underscore];
+        let number = prefix
+            .get(2..)
+            .and_then(|n| n.parse::<u32>().ok())
+            .filter(|&n| n > 0)
+            .ok_or_else(|| error(0, format!("invalid prefix {:?}", prefix)))?;
+        let kind = match &prefix[..2] {
+            "xs" => ApgcodeKind::StillLife { population: number },
+            "xp" => ApgcodeKind::Oscillator { period: number },
+            "xq" => ApgcodeKind::Spaceship { period: number },
+            _ => return Err(error(0, format!("unsupported prefix {:?}", prefix))),
+        };
+
+        let mut cells = Vec::new();
+        let (mut col, mut strip) = (0u32, 0u32);
+        let mut chars = input[underscore + 1..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            let byte = underscore + 1 + i;
+            match c {
+                'w' => col += 2,
+                'x' => col += 3,
+                'y' => {
+                    let run = chars
+                        .next()
+                        .and_then(|(_, c)| c.to_digit(36))
+                        .ok_or_else(|| error(byte, "expected a digit after `y`".to_string()))?;
+                    col += 4 + run;
+                }
+                'z' => {
+                    col = 0;
+                    strip += 1;
+                }
+                '0'..='9' | 'a'..='v' => {
+                    let column = c.to_digit(32).unwrap();
+                    for bit in 0..5 {
+                        if column & (1 << bit) != 0 {
+                            cells.push((col, strip * 5 + bit));
+                        }
+                    }
+                    col += 1;
+                }
+                c => return Err(error(byte, format!("unexpected character {:?}", c))),
+            }
+        }
+
+        if let ApgcodeKind::StillLife { population } = kind {
+            if population as usize != cells.len() {
+                return Err(error(
+                    0,
+                    format!("{} has {} cells, not {}", prefix, cells.len(), population),
+                ));
+            }
+        }
+        Ok(Apgcode { kind, cells })
+    }
+}
+
+impl fmt::Display for Apgcode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ApgcodeKind::StillLife { population } => write!(f, "xs{}", population)?,
+            ApgcodeKind::Oscillator { period } => write!(f, "xp{}", period)?,
+            ApgcodeKind::Spaceship { period } => write!(f, "xq{}", period)?,
+        }
+        write!(f, "_{}", wechsler(&self.cells))
+    }
+}
@@ -0,0 +1,265 @@
+use crate::apgcode::{self, Apgcode};
+use crate::error::UniverseError;
+use crate::infinite::InfiniteUniverse;
+use crate::pattern::{Pattern, Transform};
+use crate::rule::Rule;
+use crate::Universe;
+use fixedbitset::FixedBitSet;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// A group of live cells split off a universe by `Universe::objects`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Object {
+    /// Where the top-left corner of the pattern lies in the universe. It can
+    /// be outside the grid for an object that wraps around an edge.
+    pub col: i32,
+    pub row: i32,
+    pub pattern: Pattern,
+}
+
+/// What an object does when it is left on its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Classification {
+    StillLife,
+    Oscillator {
+        period: u32,
+    },
+    /// `dx` and `dy` are the distance covered every period, with the
+    /// direction dropped so that `dx >= dy >= 0`.
+    Spaceship {
+        period: u32,
+        dx: u32,
+        dy: u32,
+    },
+    /// Died, or did not repeat within the generations it was given.
+    Unknown,
+}
+
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Classification::StillLife => write!(f, "still life"),
+            Classification::Oscillator { period } => write!(f, "p{} oscillator", period),
+            Classification::Spaceship { period, dx, dy } => {
+                write!(f, "c/{} spaceship moving ({}, {})", period, dx, dy)
+            }
+            Classification::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+// The live cells of a pattern relative to their bounding box, sorted, and
+// the top-left corner of the box.
+fn normalize(mut cells: Vec<(i32, i32)>) -> (Vec<(u32, u32)>, i32, i32) {
+    let left = cells.iter().map(|&(col, _)| col).min().unwrap_or(0);
+    let top = cells.iter().map(|&(_, row)| row).min().unwrap_or(0);
+    cells.sort_unstable_by_key(|&(col, row)| (row, col));
+    let cells = cells
+        .into_iter()
+        .map(|(col, row)| ((col - left) as u32, (row - top) as u32))
+        .collect();
+    (cells, left, top)
+}
+
+/// Runs `pattern` on its own for up to `max_generations` and returns what it
+/// is, together with every phase of its cycle. Unknown objects come back
+/// with their starting phase only.
+pub(crate) fn classify(
+    pattern: &Pattern,
+    rule: Rule,
+    max_generations: u32,
+) -> Result<(Classification, Vec<Pattern>), UniverseError> {
+    let mut universe = InfiniteUniverse::new();
+    universe.set_rule(rule)?;
+    universe.put_pattern(pattern, 0, 0, Transform::Identity);
+
+    let mut phases: Vec<Vec<(u32, u32)>> = Vec::new();
+    let mut seen: HashMap<Vec<(u32, u32)>, (usize, i32, i32)> = HashMap::new();
+    for generation in 0..=max_generations as usize {
+        if generation > 0 {
+            universe.tick();
+        }
+        let (cells, col, row) = normalize(universe.live_cells());
+        if cells.is_empty() {
+            break;
+        }
+        if let Some(&(start, start_col, start_row)) = seen.get(&cells) {
+            let period = (generation - start) as u32;
+            let (dx, dy) = ((col - start_col).unsigned_abs(), (row - start_row).unsigned_abs());
+            let classification = match (period, dx.max(dy), dx.min(dy)) {
+                (1, 0, _) => Classification::StillLife,
+                (period, 0, _) => Classification::Oscillator { period },
+                (period, dx, dy) => Classification::Spaceship { period, dx, dy },
+            };
+            let cycle = phases.drain(start..).map(Pattern::new).collect();
+            return Ok((classification, cycle));
+        }
+        seen.insert(cells.clone(), (generation, col, row));
+        phases.push(cells);
+    }
+    Ok((Classification::Unknown, vec![pattern.clone()]))
+}
+
+/// The apgcode of an object, the same for every phase and orientation of
+/// it. Objects without one are named `zz_` and the code of their smallest
+/// orientation.
+pub(crate) fn canonical_key(classification: Classification, phases: &[Pattern]) -> String {
+    match Apgcode::canonical(classification, phases) {
+        Some(code) => code.to_string(),
+        None => format!("zz_{}", apgcode::canonical_form(phases).1),
+    }
+}
+
+impl Universe {
+    /// Splits the live cells into objects. Two cells belong to the same
+    /// object when a chain of live cells joins them in which each step is
+    /// at most `distance` cells across and down; 1 gives the usual eight
+    /// neighbors. Objects are followed across joined edges.
+    pub fn objects(&self, distance: u32) -> Vec<Object> {
+        let d = distance as i64;
+        let mut seen = FixedBitSet::with_capacity(self.cells.len());
+        let mut queue = VecDeque::new();
+        let mut objects = Vec::new();
+        for start in self.cells.ones() {
+            if seen.put(start) {
+                continue;
+            }
+            // Cells are visited with the coordinates they have when
+            // reached from `start`, so a wrapped object stays in one piece.
+            let mut cells = Vec::new();
+            let width = self.width as usize;
+            queue.push_back(((start % width) as i64, (start / width) as i64));
+            while let Some((col, row)) = queue.pop_front() {
+                cells.push((col as i32, row as i32));
+                for dy in -d..=d {
+                    for dx in -d..=d {
+                        let (width, height) = (self.width, self.height);
+                        if let Some((c, r)) = self.topology.map(col + dx, row + dy, width, height) {
+                            let idx = self.get_index(r, c);
+                            if self.cells[idx] && !seen.put(idx) {
+                                queue.push_back((col + dx, row + dy));
+                            }
+                        }
+                    }
+                }
+            }
+            let (cells, col, row) = normalize(cells);
+            objects.push(Object {
+                col,
+                row,
+                pattern: Pattern::new(cells),
+            });
+        }
+        objects
+    }
+}
+
+/// An entry of a `Census`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CensusEntry {
+    pub key: String,
+    pub classification: Classification,
+    pub count: u32,
+}
+
+/// Counts the objects left behind by soups, keyed by their apgcode.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug)]
+pub struct Census {
+    distance: u32,
+    max_generations: u32,
+    entries: HashMap<String, CensusEntry>,
+    // Keys of objects already classified under each rule, so common ones are
+    // only run once.
+    known: HashMap<(Rule, Pattern), String>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Census {
+    /// Objects are split off with `Universe::objects(distance)` and run for
+    /// up to `max_generations` to classify them.
+    pub fn new(distance: u32, max_generations: u32) -> Census {
+        Census {
+            distance,
+            max_generations,
+            entries: HashMap::new(),
+            known: HashMap::new(),
+        }
+    }
+
+    /// Counts every object in `universe`.
+    pub fn add(&mut self, universe: &Universe) -> Result<(), UniverseError> {
+        let rule = universe.rule();
+        for object in universe.objects(self.distance) {
+            let known = (rule, object.pattern);
+            let key = match self.known.get(&known) {
+                Some(key) => key.clone(),
+                None => {
+                    let (classification, phases) =
+                        classify(&known.1, rule, self.max_generations)?;
+                    let key = canonical_key(classification, &phases);
+                    self.entries.entry(key.clone()).or_insert(CensusEntry {
+                        key: key.clone(),
+                        classification,
+                        count: 0,
+                    });
+                    self.known.insert(known, key.clone());
+                    key
+                }
+            };
+            self.entries.get_mut(&key).expect("classified above").count += 1;
+        }
+        Ok(())
+    }
+
+    /// Runs `universe` until it repeats and counts what is left. Returns
+    /// false, counting nothing, if it has not settled after
+    /// `max_generations`.
+    pub fn add_soup(
+        &mut self,
+        universe: &mut Universe,
+        max_generations: u32,
+    ) -> Result<bool, UniverseError> {
+        if universe.run_until_periodic(max_generations).is_none() {
+            return Ok(false);
+        }
+        self.add(universe)?;
+        Ok(true)
+    }
+
+    /// How many objects with `key` were counted.
+    pub fn count(&self, key: &str) -> u32 {
+        self.entries.get(key).map_or(0, |entry| entry.count)
+    }
+
+    /// How many objects were counted in total.
+    pub fn total(&self) -> u32 {
+        self.entries.values().map(|entry| entry.count).sum()
+    }
+
+    /// The census as a table, most common objects first.
+    pub fn table(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Census {
+    /// The entries, most common first and then by key.
+    pub fn entries(&self) -> Vec<&CensusEntry> {
+        let mut entries: Vec<_> = self.entries.values().collect();
+        entries.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+        entries
+    }
+}
+
+impl fmt::Display for Census {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for entry in self.entries() {
+            writeln!(f, "{:>8}  {}  {}", entry.count, entry.key, entry.classification)?;
+        }
+        Ok(())
+    }
+}
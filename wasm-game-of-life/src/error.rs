@@ -0,0 +1,122 @@
+use crate::rule::{Rule, RuleParseError};
+use crate::topology::Topology;
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::JsValue;
+
+/// An error found while reading a pattern file. `line` and `column` are
+/// 1-based and point at the offending character.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(line: usize, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UniverseError {
+    /// A cell coordinate lies outside the universe.
+    OutOfBounds {
+        row: u32,
+        col: u32,
+        width: u32,
+        height: u32,
+    },
+    /// The universe would be empty, or too large to index with `u32`.
+    InvalidSize { width: u32, height: u32 },
+    /// A jump of `2^k` generations where `k` is larger than `MAX_STEP_POW2`.
+    StepTooLarge(u32),
+    InvalidRule(RuleParseError),
+    /// A rule the universe cannot run, such as a `B0` rule on the infinite
+    /// plane.
+    UnsupportedRule(Rule),
+    InvalidTopology(String),
+    /// An operation that is not available on this topology.
+    UnsupportedTopology(Topology),
+    Parse(ParseError),
+}
+
+impl UniverseError {
+    pub(crate) fn check_size(width: u32, height: u32) -> Result<(), UniverseError> {
+        match width.checked_mul(height) {
+            Some(size) if size > 0 => Ok(()),
+            _ => Err(UniverseError::InvalidSize { width, height }),
+        }
+    }
+}
+
+impl fmt::Display for UniverseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UniverseError::OutOfBounds {
+                row,
+                col,
+                width,
+                height,
+            } => write!(
+                f,
+                "cell ({}, {}) is outside the {}x{} universe",
+                row, col, width, height
+            ),
+            UniverseError::InvalidSize { width, height } => {
+                write!(f, "invalid universe size {}x{}", width, height)
+            }
+            UniverseError::StepTooLarge(k) => write!(f, "cannot advance 2^{} generations", k),
+            UniverseError::InvalidRule(err) => write!(f, "invalid rule: {}", err),
+            UniverseError::UnsupportedRule(rule) => write!(f, "unsupported rule {}", rule),
+            UniverseError::InvalidTopology(reason) => write!(f, "invalid topology: {}", reason),
+            UniverseError::UnsupportedTopology(topology) => {
+                write!(f, "not supported on a {}", topology)
+            }
+            UniverseError::Parse(err) => write!(f, "invalid pattern: {}", err),
+        }
+    }
+}
+
+impl Error for UniverseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            UniverseError::InvalidRule(err) => Some(err),
+            UniverseError::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<RuleParseError> for UniverseError {
+    fn from(err: RuleParseError) -> Self {
+        UniverseError::InvalidRule(err)
+    }
+}
+
+impl From<ParseError> for UniverseError {
+    fn from(err: ParseError) -> Self {
+        UniverseError::Parse(err)
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<UniverseError> for JsValue {
+    fn from(err: UniverseError) -> Self {
+        js_sys::Error::new(&err.to_string()).into()
+    }
+}
@@ -0,0 +1,278 @@
+use crate::rule::Rule;
+use std::collections::HashMap;
+
+type NodeId = u32;
+
+const DEAD: NodeId = 0;
+const ALIVE: NodeId = 1;
+
+// Once the node table grows past this, the engine is thrown away and rebuilt
+// on the next step instead of growing without bound.
+const MAX_NODES: usize = 1 << 22;
+
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    nw: NodeId,
+    ne: NodeId,
+    sw: NodeId,
+    se: NodeId,
+    level: u8,
+    population: u64,
+}
+
+/// A HashLife engine: a quadtree whose nodes are hash-consed, so that equal
+/// squares share one node, and whose results are memoized per node.
+///
+/// A node of level `n` covers a `2^n` x `2^n` square. Leaves are single cells.
+pub(crate) struct HashLife {
+    rule: Rule,
+    nodes: Vec<Node>,
+    table: HashMap<[NodeId; 4], NodeId>,
+    results: HashMap<(NodeId, u8), NodeId>,
+}
+
+impl HashLife {
+    pub(crate) fn new(rule: Rule) -> HashLife {
+        let leaf = |population| Node {
+            nw: DEAD,
+            ne: DEAD,
+            sw: DEAD,
+            se: DEAD,
+            level: 0,
+            population,
+        };
+        HashLife {
+            rule,
+            nodes: vec![leaf(0), leaf(1)],
+            table: HashMap::new(),
+            results: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        self.nodes.len() > MAX_NODES
+    }
+
+    fn node(&self, id: NodeId) -> Node {
+        self.nodes[id as usize]
+    }
+
+    fn join(&mut self, nw: NodeId, ne: NodeId, sw: NodeId, se: NodeId) -> NodeId {
+        let key = [nw, ne, sw, se];
+        if let Some(&id) = self.table.get(&key) {
+            return id;
+        }
+        let population = key
+            .iter()
+            .fold(0u64, |sum, &child| sum.saturating_add(self.node(child).population));
+        let id = self.nodes.len() as NodeId;
+        self.nodes.push(Node {
+            nw,
+            ne,
+            sw,
+            se,
+            level: self.node(nw).level + 1,
+            population,
+        });
+        self.table.insert(key, id);
+        id
+    }
+
+    // The level `n - 1` square centered in a level `n` node.
+    fn center(&mut self, id: NodeId) -> NodeId {
+        let n = self.node(id);
+        let (nw, ne, sw, se) = (self.node(n.nw), self.node(n.ne), self.node(n.sw), self.node(n.se));
+        self.join(nw.se, ne.sw, sw.ne, se.nw)
+    }
+
+    fn center_horizontal(&mut self, west: NodeId, east: NodeId) -> NodeId {
+        let (w, e) = (self.node(west), self.node(east));
+        self.join(w.ne, e.nw, w.se, e.sw)
+    }
+
+    fn center_vertical(&mut self, north: NodeId, south: NodeId) -> NodeId {
+        let (n, s) = (self.node(north), self.node(south));
+        self.join(n.sw, n.se, s.nw, s.ne)
+    }
+
+    // One generation of the 2x2 center of a 4x4 node.
+    fn base_case(&mut self, id: NodeId) -> NodeId {
+        let mut cells = [[false; 4]; 4];
+        let n = self.node(id);
+        for (quadrant, (x0, y0)) in [(n.nw, (0, 0)), (n.ne, (2, 0)), (n.sw, (0, 2)), (n.se, (2, 2))] {
+            let q = self.node(quadrant);
+            cells[y0][x0] = q.nw == ALIVE;
+            cells[y0][x0 + 1] = q.ne == ALIVE;
+            cells[y0 + 1][x0] = q.sw == ALIVE;
+            cells[y0 + 1][x0 + 1] = q.se == ALIVE;
+        }
+
+        let mut next = [DEAD; 4];
+        for (i, &(x, y)) in [(1, 1), (2, 1), (1, 2), (2, 2)].iter().enumerate() {
+            let neighbors = cells[y - 1..=y + 1]
+                .iter()
+                .flat_map(|row| &row[x - 1..=x + 1])
+                .filter(|&&alive| alive)
+                .count()
+                - cells[y][x] as usize;
+            if self.rule.next_state(cells[y][x], neighbors as u8) {
+                next[i] = ALIVE;
+            }
+        }
+        self.join(next[0], next[1], next[2], next[3])
+    }
+
+    /// Returns the center half of `id` advanced by `2^j` generations, where
+    /// `j` is at most the level of `id` minus 2.
+    fn advance(&mut self, id: NodeId, j: u8) -> NodeId {
+        if let Some(&result) = self.results.get(&(id, j)) {
+            return result;
+        }
+        let n = self.node(id);
+        debug_assert!(n.level >= 2 && j <= n.level - 2);
+
+        let result = if n.level == 2 {
+            self.base_case(id)
+        } else {
+            let n00 = n.nw;
+            let n01 = self.center_horizontal(n.nw, n.ne);
+            let n02 = n.ne;
+            let n10 = self.center_vertical(n.nw, n.sw);
+            let n11 = self.center(id);
+            let n12 = self.center_vertical(n.ne, n.se);
+            let n20 = n.sw;
+            let n21 = self.center_horizontal(n.sw, n.se);
+            let n22 = n.se;
+
+            // At full speed both halves of the jump advance time, otherwise
+            // only the second one does.
+            let full_speed = j == n.level - 2;
+            let step = |engine: &mut HashLife, id| {
+                if full_speed {
+                    engine.advance(id, j - 1)
+                } else {
+                    engine.center(id)
+                }
+            };
+            let r00 = step(self, n00);
+            let r01 = step(self, n01);
+            let r02 = step(self, n02);
+            let r10 = step(self, n10);
+            let r11 = step(self, n11);
+            let r12 = step(self, n12);
+            let r20 = step(self, n20);
+            let r21 = step(self, n21);
+            let r22 = step(self, n22);
+
+            let j = if full_speed { j - 1 } else { j };
+            let nw = self.join(r00, r01, r10, r11);
+            let ne = self.join(r01, r02, r11, r12);
+            let sw = self.join(r10, r11, r20, r21);
+            let se = self.join(r11, r12, r21, r22);
+            let nw = self.advance(nw, j);
+            let ne = self.advance(ne, j);
+            let sw = self.advance(sw, j);
+            let se = self.advance(se, j);
+            self.join(nw, ne, sw, se)
+        };
+        self.results.insert((id, j), result);
+        result
+    }
+
+    /// Advances a `width` x `height` torus by `2^k` generations. `is_alive`
+    /// reads the current cells, and `set` receives every live cell of the
+    /// result as `(col, row)`.
+    pub(crate) fn step_torus(
+        &mut self,
+        width: u32,
+        height: u32,
+        k: u8,
+        is_alive: impl Fn(u32, u32) -> bool,
+        mut set: impl FnMut(u32, u32),
+    ) {
+        // The root tiles the plane with copies of the torus. Its result is the
+        // center half, which must still hold a full copy.
+        let span = width.max(height).next_power_of_two().trailing_zeros() as u8;
+        let level = (k + 2).max(span + 1).max(2);
+
+        // (2^l mod width, 2^l mod height) for every level, to find where the
+        // quadrants of a node start on the torus.
+        let mut steps = Vec::with_capacity(level as usize + 1);
+        let (mut dx, mut dy) = (1 % width as u64, 1 % height as u64);
+        for _ in 0..=level {
+            steps.push((dx as u32, dy as u32));
+            dx = dx * 2 % width as u64;
+            dy = dy * 2 % height as u64;
+        }
+
+        let mut built = HashMap::new();
+        let root = self.tile(level, 0, 0, width, height, &steps, &is_alive, &mut built);
+        let result = self.advance(root, k);
+
+        // The result starts at (2^(level - 2), 2^(level - 2)) in root coordinates.
+        let (offset_x, offset_y) = steps[level as usize - 2];
+        self.for_each_alive(result, 0, 0, width, height, &mut |x, y| {
+            set((x + offset_x) % width, (y + offset_y) % height)
+        });
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn tile(
+        &mut self,
+        level: u8,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        steps: &[(u32, u32)],
+        is_alive: &impl Fn(u32, u32) -> bool,
+        built: &mut HashMap<(u8, u32, u32), NodeId>,
+    ) -> NodeId {
+        if level == 0 {
+            return if is_alive(x, y) { ALIVE } else { DEAD };
+        }
+        if let Some(&id) = built.get(&(level, x, y)) {
+            return id;
+        }
+        let (dx, dy) = steps[level as usize - 1];
+        let (east, south) = ((x + dx) % width, (y + dy) % height);
+        let nw = self.tile(level - 1, x, y, width, height, steps, is_alive, built);
+        let ne = self.tile(level - 1, east, y, width, height, steps, is_alive, built);
+        let sw = self.tile(level - 1, x, south, width, height, steps, is_alive, built);
+        let se = self.tile(level - 1, east, south, width, height, steps, is_alive, built);
+        let id = self.join(nw, ne, sw, se);
+        built.insert((level, x, y), id);
+        id
+    }
+
+    // Calls `f` for every live cell of `id` inside `[0, width) x [0, height)`,
+    // with the node's top-left corner at `(x, y)`.
+    fn for_each_alive(
+        &self,
+        id: NodeId,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        f: &mut impl FnMut(u32, u32),
+    ) {
+        let n = self.node(id);
+        if n.population == 0 || x >= width || y >= height {
+            return;
+        }
+        if n.level == 0 {
+            f(x, y);
+            return;
+        }
+        let half = 1u32.checked_shl(n.level as u32 - 1).unwrap_or(u32::MAX);
+        let (east, south) = (x.saturating_add(half), y.saturating_add(half));
+        self.for_each_alive(n.nw, x, y, width, height, f);
+        self.for_each_alive(n.ne, east, y, width, height, f);
+        self.for_each_alive(n.sw, x, south, width, height, f);
+        self.for_each_alive(n.se, east, south, width, height, f);
+    }
+}
@@ -0,0 +1,130 @@
+use fixedbitset::FixedBitSet;
+use std::collections::VecDeque;
+
+/// The cells that flipped between two states, so that applying it again
+/// goes back and forth between them.
+pub(crate) enum Delta {
+    /// Indices of the flipped cells, for small changes.
+    Sparse(Vec<u32>),
+    /// The XOR of the two states, once a list of indices would be larger.
+    Dense(FixedBitSet),
+}
+
+impl Delta {
+    pub(crate) fn between(before: &FixedBitSet, after: &FixedBitSet) -> Delta {
+        let mut xor = before.clone();
+        for (block, &other) in xor.as_mut_slice().iter_mut().zip(after.as_slice()) {
+            *block ^= other;
+        }
+        // One 32-bit index costs as much as 32 cells of the XOR.
+        if xor.count_ones(..) * 32 < xor.len() {
+            Delta::Sparse(xor.ones().map(|idx| idx as u32).collect())
+        } else {
+            Delta::Dense(xor)
+        }
+    }
+
+    pub(crate) fn from_indices(indices: &[u32], size: usize) -> Delta {
+        if indices.len() * 32 < size {
+            Delta::Sparse(indices.to_vec())
+        } else {
+            let mut xor = FixedBitSet::with_capacity(size);
+            xor.extend(indices.iter().map(|&idx| idx as usize));
+            Delta::Dense(xor)
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Delta::Sparse(indices) => indices.is_empty(),
+            Delta::Dense(xor) => xor.count_ones(..) == 0,
+        }
+    }
+
+    /// Flips the cells in `cells` and calls `flipped` with each index.
+    pub(crate) fn apply(&self, cells: &mut FixedBitSet, mut flipped: impl FnMut(u32)) {
+        match self {
+            Delta::Sparse(indices) => {
+                for &idx in indices {
+                    let idx_usize = idx as usize;
+                    cells.set(idx_usize, !cells[idx_usize]);
+                    flipped(idx);
+                }
+            }
+            Delta::Dense(xor) => {
+                for (block, &other) in cells.as_mut_slice().iter_mut().zip(xor.as_slice()) {
+                    *block ^= other;
+                }
+                xor.ones().for_each(|idx| flipped(idx as u32));
+            }
+        }
+    }
+}
+
+/// One undoable step: a tick, a jump, or an edit when `generations` is 0.
+pub(crate) struct Entry {
+    pub(crate) delta: Delta,
+    pub(crate) generations: u64,
+}
+
+/// A bounded undo/redo history. It holds nothing until it is given a
+/// capacity, so by default recording costs nothing.
+#[derive(Default)]
+pub(crate) struct History {
+    capacity: usize,
+    past: VecDeque<Entry>,
+    future: Vec<Entry>,
+}
+
+impl History {
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Keeps at most `capacity` steps each way, dropping the ones furthest
+    /// from the current generation.
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.past.len() > capacity {
+            self.past.pop_front();
+        }
+        // The next step to redo is at the end.
+        self.future.drain(..self.future.len().saturating_sub(capacity));
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.past.clear();
+        self.future.clear();
+    }
+
+    /// Records a new step, which makes anything undone so far unreachable.
+    pub(crate) fn record(&mut self, delta: Delta, generations: u64) {
+        if !self.is_enabled() || (generations == 0 && delta.is_empty()) {
+            return;
+        }
+        self.future.clear();
+        if self.past.len() == self.capacity {
+            self.past.pop_front();
+        }
+        self.past.push_back(Entry { delta, generations });
+    }
+
+    pub(crate) fn take_undo(&mut self) -> Option<Entry> {
+        self.past.pop_back()
+    }
+
+    pub(crate) fn push_redo(&mut self, entry: Entry) {
+        self.future.push(entry);
+    }
+
+    pub(crate) fn take_redo(&mut self) -> Option<Entry> {
+        self.future.pop()
+    }
+
+    pub(crate) fn push_undo(&mut self, entry: Entry) {
+        if self.past.len() == self.capacity {
+            self.past.pop_front();
+        }
+        self.past.push_back(entry);
+    }
+}
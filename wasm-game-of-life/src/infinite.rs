@@ -0,0 +1,247 @@
+use crate::error::UniverseError;
+use crate::kernel;
+use crate::pattern::{Pattern, Transform};
+use crate::render::{self, RenderOptions};
+use crate::rle::Rle;
+use crate::rule::Rule;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+const CHUNK: i32 = 64;
+
+// Keys of the chunks whose cells all have `i32` coordinates.
+const MIN_KEY: i32 = i32::MIN / CHUNK;
+const MAX_KEY: i32 = i32::MAX / CHUNK;
+
+// 64x64 cells; bit `col` of word `row` is the cell at (col, row).
+type Chunk = [u64; CHUNK as usize];
+
+const EMPTY: Chunk = [0; CHUNK as usize];
+
+/// The smallest rectangle holding every live cell.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub col: i32,
+    pub row: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A universe on the infinite plane. Only 64x64 chunks that hold live cells
+/// are stored, so patterns can grow without ever meeting their own exhaust.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug, Default)]
+pub struct InfiniteUniverse {
+    chunks: HashMap<(i32, i32), Box<Chunk>>,
+    rule: Rule,
+}
+
+fn split(col: i32, row: i32) -> ((i32, i32), usize, u32) {
+    let key = (col.div_euclid(CHUNK), row.div_euclid(CHUNK));
+    (
+        key,
+        row.rem_euclid(CHUNK) as usize,
+        col.rem_euclid(CHUNK) as u32,
+    )
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl InfiniteUniverse {
+    pub fn new() -> InfiniteUniverse {
+        InfiniteUniverse::default()
+    }
+
+    /// Sets the rule from B/S notation. Rules with `B0` are rejected, since
+    /// they would fill the whole plane in one generation.
+    pub fn set_rule_string(&mut self, rule: &str) -> Result<(), UniverseError> {
+        self.set_rule(rule.parse()?)
+    }
+
+    pub fn rule_string(&self) -> String {
+        self.rule.to_string()
+    }
+
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+    }
+
+    pub fn is_alive(&self, col: i32, row: i32) -> bool {
+        let (key, y, x) = split(col, row);
+        self.chunks
+            .get(&key)
+            .is_some_and(|chunk| chunk[y] & (1 << x) != 0)
+    }
+
+    pub fn set_cell(&mut self, col: i32, row: i32, alive: bool) {
+        let (key, y, x) = split(col, row);
+        if alive {
+            self.chunks.entry(key).or_insert_with(|| Box::new(EMPTY))[y] |= 1 << x;
+        } else if let Some(chunk) = self.chunks.get_mut(&key) {
+            chunk[y] &= !(1 << x);
+        }
+    }
+
+    /// Sets the cells of `pattern` with its top-left corner at `col` and
+    /// `row`. Cells that would lie past the `i32` range are left out.
+    pub fn put_pattern(&mut self, pattern: &Pattern, col: i32, row: i32, transform: Transform) {
+        for &(x, y) in pattern.transformed(transform).cells() {
+            let (col, row) = (col.checked_add_unsigned(x), row.checked_add_unsigned(y));
+            if let (Some(col), Some(row)) = (col, row) {
+                self.set_cell(col, row, true);
+            }
+        }
+    }
+
+    /// Replaces the contents with an RLE pattern whose top-left corner is
+    /// at the origin.
+    pub fn load_rle(&mut self, input: &str) -> Result<(), UniverseError> {
+        let rle: Rle = input.parse()?;
+        if let Some(rule) = rle.rule {
+            self.set_rule(rule)?;
+        }
+        self.clear();
+        self.put_pattern(&rle.into(), 0, 0, Transform::Identity);
+        Ok(())
+    }
+
+    pub fn population(&self) -> u64 {
+        self.chunks
+            .values()
+            .flat_map(|chunk| chunk.iter())
+            .map(|word| word.count_ones() as u64)
+            .sum()
+    }
+
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let mut bounds: Option<(i32, i32, i32, i32)> = None;
+        for (&(cx, cy), chunk) in &self.chunks {
+            for (y, &word) in chunk.iter().enumerate() {
+                if word == 0 {
+                    continue;
+                }
+                let row = cy * CHUNK + y as i32;
+                let left = cx * CHUNK + word.trailing_zeros() as i32;
+                let right = cx * CHUNK + 63 - word.leading_zeros() as i32;
+                bounds = Some(match bounds {
+                    None => (left, row, right, row),
+                    Some((l, t, r, b)) => (l.min(left), t.min(row), r.max(right), b.max(row)),
+                });
+            }
+        }
+        bounds.map(|(left, top, right, bottom)| BoundingBox {
+            col: left,
+            row: top,
+            width: right.abs_diff(left).saturating_add(1),
+            height: bottom.abs_diff(top).saturating_add(1),
+        })
+    }
+
+    /// Advances one generation. Cells born past the `i32` range are lost.
+    pub fn tick(&mut self) {
+        let keys = MIN_KEY..=MAX_KEY;
+        let mut candidates = HashSet::new();
+        for &(cx, cy) in self.chunks.keys() {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let key = (cx + dx, cy + dy);
+                    if keys.contains(&key.0) && keys.contains(&key.1) {
+                        candidates.insert(key);
+                    }
+                }
+            }
+        }
+
+        let mut next = HashMap::with_capacity(self.chunks.len());
+        for key in candidates {
+            let chunk = self.next_chunk(key);
+            if chunk.iter().any(|&word| word != 0) {
+                next.insert(key, chunk);
+            }
+        }
+        self.chunks = next;
+    }
+
+    /// Renders the bounding box of the live cells. A viewport in `options`
+    /// is relative to the top-left corner of the bounding box.
+    pub fn render(&self, options: &RenderOptions) -> String {
+        let mut out = String::new();
+        self.write_cells(&mut out, options)
+            .expect("writing to a String cannot fail");
+        out
+    }
+}
+
+impl InfiniteUniverse {
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    pub fn set_rule(&mut self, rule: Rule) -> Result<(), UniverseError> {
+        if rule.birth() & 1 != 0 {
+            return Err(UniverseError::UnsupportedRule(rule));
+        }
+        self.rule = rule;
+        Ok(())
+    }
+
+    /// Live cells as `(col, row)` pairs, in no particular order.
+    pub fn live_cells(&self) -> Vec<(i32, i32)> {
+        let mut cells = Vec::new();
+        for (&(cx, cy), chunk) in &self.chunks {
+            for (y, &word) in chunk.iter().enumerate() {
+                let mut bits = word;
+                while bits != 0 {
+                    let x = bits.trailing_zeros() as i32;
+                    cells.push((cx * CHUNK + x, cy * CHUNK + y as i32));
+                    bits &= bits - 1;
+                }
+            }
+        }
+        cells
+    }
+
+    fn next_chunk(&self, (cx, cy): (i32, i32)) -> Box<Chunk> {
+        let get = |dx: i32, dy: i32| -> &Chunk {
+            self.chunks
+                .get(&(cx + dx, cy + dy))
+                .map_or(&EMPTY, |chunk| chunk)
+        };
+        let around = [-1, 0, 1].map(|dy| [-1, 0, 1].map(|dx| get(dx, dy)));
+
+        // The west, center and east words of row `y`, which may lie in the
+        // chunks above or below when `y` is -1 or 64.
+        let row = |y: i32| -> [u64; 3] {
+            let (band, y) = match y {
+                -1 => (&around[0], CHUNK as usize - 1),
+                CHUNK => (&around[2], 0),
+                y => (&around[1], y as usize),
+            };
+            [band[0][y], band[1][y], band[2][y]]
+        };
+
+        let mut next = Box::new(EMPTY);
+        for y in 0..CHUNK {
+            next[y as usize] = kernel::next_row(self.rule, row(y - 1), row(y), row(y + 1));
+        }
+        next
+    }
+
+    fn write_cells(&self, out: &mut impl fmt::Write, options: &RenderOptions) -> fmt::Result {
+        let bounds = match self.bounding_box() {
+            Some(bounds) => bounds,
+            None => return Ok(()),
+        };
+        render::write_cells(out, options, bounds.width, bounds.height, |row, col| {
+            self.is_alive(bounds.col + col as i32, bounds.row + row as i32)
+        })
+    }
+}
+
+impl fmt::Display for InfiniteUniverse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_cells(f, &RenderOptions::default())
+    }
+}
@@ -0,0 +1,216 @@
+// Bit-parallel Life: every bit of a word is one cell, and neighbor counts are
+// summed for all of them at once with full adders.
+
+use crate::rule::Rule;
+use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
+
+/// A bundle of one or more 64-cell words that the adders work on at once.
+/// Shifts move the bits within each word.
+pub(crate) trait Lanes:
+    Copy
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + BitXor<Output = Self>
+    + Not<Output = Self>
+    + Shl<u32, Output = Self>
+    + Shr<u32, Output = Self>
+{
+    /// Number of 64-bit words in one value.
+    const WORDS: usize;
+
+    fn zero() -> Self;
+
+    /// Builds a value whose word `i` is `f(i)`.
+    fn from_fn(f: impl Fn(usize) -> u64) -> Self;
+
+    /// Loads the first `WORDS` words of `words`, padding with zeros past the
+    /// end.
+    fn load(words: &[u64]) -> Self;
+
+    /// Stores as many words as fit into `out`.
+    fn store(self, out: &mut [u64]);
+
+    /// Calls `f` with the index and value of every word.
+    fn for_each_word(self, f: impl FnMut(usize, u64));
+}
+
+impl Lanes for u64 {
+    const WORDS: usize = 1;
+
+    fn zero() -> Self {
+        0
+    }
+
+    fn from_fn(f: impl Fn(usize) -> u64) -> Self {
+        f(0)
+    }
+
+    fn load(words: &[u64]) -> Self {
+        words.first().copied().unwrap_or(0)
+    }
+
+    fn store(self, out: &mut [u64]) {
+        if let Some(word) = out.first_mut() {
+            *word = self;
+        }
+    }
+
+    fn for_each_word(self, mut f: impl FnMut(usize, u64)) {
+        f(0, self)
+    }
+}
+
+fn add3<L: Lanes>(a: L, b: L, c: L) -> (L, L) {
+    let ab = a ^ b;
+    (ab ^ c, (a & b) | (c & ab))
+}
+
+/// Returns the next state of the cells in `center`, given their 8 neighbors
+/// already shifted into the matching bit positions.
+pub(crate) fn next_word<L: Lanes>(rule: Rule, neighbors: [L; 8], center: L) -> L {
+    let [n0, n1, n2, n3, n4, n5, n6, n7] = neighbors;
+
+    // Sum the eight 1-bit inputs into a 4-bit count, bit-sliced in b0..b3.
+    let (s0, c0) = add3(n0, n1, n2);
+    let (s1, c1) = add3(n3, n4, n5);
+    let (s2, c2) = (n6 ^ n7, n6 & n7);
+    let (b0, c3) = add3(s0, s1, s2);
+    let (t, d0) = add3(c0, c1, c2);
+    let (b1, d1) = (t ^ c3, t & c3);
+    let (b2, b3) = (d0 ^ d1, d0 & d1);
+
+    let mut birth = L::zero();
+    let mut survival = L::zero();
+    for count in 0..=8 {
+        let bit = |b: L, i: u32| if count & (1 << i) != 0 { b } else { !b };
+        let equal = bit(b0, 0) & bit(b1, 1) & bit(b2, 2) & bit(b3, 3);
+        if rule.birth() & (1 << count) != 0 {
+            birth = birth | equal;
+        }
+        if rule.survival() & (1 << count) != 0 {
+            survival = survival | equal;
+        }
+    }
+    (!center & birth) | (center & survival)
+}
+
+/// Next state of a row of 64 cells. Each argument holds the `(west, center,
+/// east)` words of a row, where bit `i` is column `i` and the west and east
+/// words are the neighboring words on that row.
+pub(crate) fn next_row(rule: Rule, above: [u64; 3], row: [u64; 3], below: [u64; 3]) -> u64 {
+    let west = |[w, c, _]: [u64; 3]| (c << 1) | (w >> 63);
+    let east = |[_, c, e]: [u64; 3]| (c >> 1) | (e << 63);
+    next_word(
+        rule,
+        [
+            west(above),
+            above[1],
+            east(above),
+            west(row),
+            east(row),
+            west(below),
+            below[1],
+            east(below),
+        ],
+        row[1],
+    )
+}
+
+// Whether `chunk` is a full run of consecutive indices.
+fn is_run<L: Lanes>(chunk: &[usize]) -> bool {
+    chunk.len() == L::WORDS && chunk[chunk.len() - 1] == chunk[0] + L::WORDS - 1
+}
+
+// The words just before, at and just after the words of `buf` listed in
+// `chunk`. A run of consecutive indices is loaded straight from `buf`, which
+// is the common case; anything else is gathered word by word.
+fn load_around<L: Lanes>(buf: &[u64], chunk: &[usize]) -> [L; 3] {
+    let first = chunk[0];
+    if first > 0 && is_run::<L>(chunk) {
+        return [
+            L::load(&buf[first - 1..]),
+            L::load(&buf[first..]),
+            L::load(buf.get(first + 1..).unwrap_or_default()),
+        ];
+    }
+    let word = |i: usize| buf.get(i).copied().unwrap_or(0);
+    [
+        L::from_fn(|k| chunk.get(k).map_or(0, |&i| i.checked_sub(1).map_or(0, word))),
+        L::from_fn(|k| chunk.get(k).map_or(0, |&i| word(i))),
+        L::from_fn(|k| chunk.get(k).map_or(0, |&i| word(i + 1))),
+    ]
+}
+
+/// Next state of the words of `row` listed in `indices`, `L::WORDS` words at
+/// a time, written to the same positions in `out`. `above`, `row`, `below`
+/// and `out` have the same length, and cells past either end of a row count
+/// as dead.
+pub(crate) fn next_words<L: Lanes>(
+    rule: Rule,
+    above: &[u64],
+    row: &[u64],
+    below: &[u64],
+    indices: &[usize],
+    out: &mut [u64],
+) {
+    let west = |[before, word, _]: [L; 3]| (word << 1) | (before >> 63);
+    let east = |[_, word, after]: [L; 3]| (word >> 1) | (after << 63);
+
+    for chunk in indices.chunks(L::WORDS) {
+        let (a, r, b) = (
+            load_around::<L>(above, chunk),
+            load_around::<L>(row, chunk),
+            load_around::<L>(below, chunk),
+        );
+        let neighbors = [west(a), a[1], east(a), west(r), east(r), west(b), b[1], east(b)];
+        let next = next_word(rule, neighbors, r[1]);
+        if is_run::<L>(chunk) {
+            next.store(&mut out[chunk[0]..]);
+        } else {
+            next.for_each_word(|k, bits| {
+                if let Some(&i) = chunk.get(k) {
+                    out[i] = bits;
+                }
+            });
+        }
+    }
+}
+
+/// Reads `len` bits (at most 64) starting at bit `start` of `blocks`.
+pub(crate) fn read_bits(blocks: &[u32], start: usize, len: usize) -> u64 {
+    let block = |i: usize| blocks.get(i).copied().unwrap_or(0) as u64;
+    let (i, shift) = (start / 32, start % 32);
+    let low = block(i) | (block(i + 1) << 32);
+    let bits = if shift == 0 {
+        low
+    } else {
+        (low >> shift) | (block(i + 2) << (64 - shift))
+    };
+    bits & mask(len)
+}
+
+/// Overwrites the `len` bits starting at bit `start` of `blocks` with the low
+/// bits of `bits`.
+pub(crate) fn write_bits(blocks: &mut [u32], start: usize, len: usize, bits: u64) {
+    let (i, shift) = (start / 32, start % 32);
+    let spread = |bits: u64| {
+        [
+            (bits << shift) as u32,
+            (bits >> (32 - shift)) as u32,
+            if shift == 0 { 0 } else { (bits >> (64 - shift)) as u32 },
+        ]
+    };
+    let (parts, masks) = (spread(bits & mask(len)), spread(mask(len)));
+    for ((block, part), covered) in blocks[i..].iter_mut().zip(parts).zip(masks) {
+        *block = (*block & !covered) | part;
+    }
+}
+
+/// The low `len` bits set.
+pub(crate) fn mask(len: usize) -> u64 {
+    if len >= 64 {
+        !0
+    } else {
+        (1 << len) - 1
+    }
+}
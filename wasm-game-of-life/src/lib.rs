@@ -1,35 +1,66 @@
+#[cfg(feature = "alloc-counter")]
+mod alloc;
+mod apgcode;
+mod census;
+mod error;
+mod hashlife;
+mod history;
+mod infinite;
+mod kernel;
+mod pattern;
+mod period;
+mod plaintext;
+mod platform;
+mod render;
+mod rle;
+mod rng;
+mod rule;
+#[cfg(feature = "simd")]
+mod simd;
+mod stats;
+mod topology;
 mod utils;
 
+pub use apgcode::{Apgcode, ApgcodeKind};
+pub use census::{Census, CensusEntry, Classification, Object};
+pub use error::{ParseError, UniverseError};
+pub use infinite::{BoundingBox, InfiniteUniverse};
+pub use pattern::{Pattern, Transform};
+pub use period::{PeriodDetector, Periodicity};
+pub use plaintext::Plaintext;
+pub use platform::Timer;
+pub use render::{RenderOptions, Viewport};
+pub use rle::Rle;
+pub use rng::Pcg32;
+pub use rule::{Rule, RuleParseError};
+pub use stats::StepStats;
+pub use topology::Topology;
+
 use fixedbitset::FixedBitSet;
-use js_sys::Math;
+use hashlife::HashLife;
+use history::{Delta, History};
+use platform::{random_seed, Stopwatch};
+use stats::{Sample, Series};
 use std::fmt;
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
-use web_sys::console;
-
-pub struct Timer<'a> {
-    name: &'a str,
-}
-
-impl<'a> Timer<'a> {
-    pub fn new(name: &'a str) -> Timer<'a> {
-        console::time_with_label(name);
-        Timer { name }
-    }
-}
-
-impl<'a> Drop for Timer<'a> {
-    fn drop(&mut self) {
-        console::time_end_with_label(self.name);
-    }
-}
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
-#[cfg(feature = "wee_alloc")]
+#[cfg(all(feature = "wee_alloc", not(feature = "alloc-counter")))]
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
-#[wasm_bindgen]
+// With `alloc-counter`, count allocations on top of the allocator that would
+// otherwise be used.
+#[cfg(all(feature = "alloc-counter", feature = "wee_alloc"))]
+#[global_allocator]
+static ALLOC: alloc::Counting = alloc::Counting(wee_alloc::WeeAlloc::INIT);
+#[cfg(all(feature = "alloc-counter", not(feature = "wee_alloc")))]
+#[global_allocator]
+static ALLOC: alloc::Counting = alloc::Counting(std::alloc::System);
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Cell {
@@ -37,28 +68,99 @@ pub enum Cell {
     Alive = 1,
 }
 
-#[wasm_bindgen]
+impl From<bool> for Cell {
+    fn from(alive: bool) -> Cell {
+        if alive {
+            Cell::Alive
+        } else {
+            Cell::Dead
+        }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[repr(u8)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum InitType {
+    #[default]
     Random = 0,
     Clear = 1,
 }
 
-impl Default for InitType {
-    fn default() -> Self {
-        InitType::Random
+/// Which part of the universe stays in place when it is resized.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Anchor {
+    #[default]
+    TopLeft = 0,
+    Top = 1,
+    TopRight = 2,
+    Left = 3,
+    Center = 4,
+    Right = 5,
+    BottomLeft = 6,
+    Bottom = 7,
+    BottomRight = 8,
+}
+
+impl Anchor {
+    // offset of the old grid inside the new one, per axis
+    fn offset(self, old_width: u32, old_height: u32, width: u32, height: u32) -> (i64, i64) {
+        let dx = width as i64 - old_width as i64;
+        let dy = height as i64 - old_height as i64;
+        let col = match self {
+            Anchor::TopLeft | Anchor::Left | Anchor::BottomLeft => 0,
+            Anchor::Top | Anchor::Center | Anchor::Bottom => dx / 2,
+            Anchor::TopRight | Anchor::Right | Anchor::BottomRight => dx,
+        };
+        let row = match self {
+            Anchor::TopLeft | Anchor::Top | Anchor::TopRight => 0,
+            Anchor::Left | Anchor::Center | Anchor::Right => dy / 2,
+            Anchor::BottomLeft | Anchor::Bottom | Anchor::BottomRight => dy,
+        };
+        (col, row)
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 pub struct Universe {
     width: u32,
     height: u32,
     cells: FixedBitSet,
+    // The buffer the next generation is written to, swapped with `cells`.
+    next: FixedBitSet,
+    // Tiles that changed in the last generation or were edited since, and
+    // the tiles the next `tick` recomputes.
+    changed: FixedBitSet,
+    active: FixedBitSet,
+    // Indices of the cells that flipped in the last `tick`, `step_pow2`,
+    // `undo` or `redo`.
+    flipped: Vec<u32>,
+    // Scratch rows and word indices for `tick_interior`.
+    rows: Vec<u64>,
+    words: Vec<usize>,
+    rule: Rule,
+    topology: Topology,
+    seed: u64,
+    rng: Pcg32,
+    hashlife: Option<HashLife>,
+    history: History,
+    generation: u64,
+    series: Series,
     perf: bool,
+    #[cfg(feature = "simd")]
+    simd: bool,
+    #[cfg(feature = "alloc-counter")]
+    tick_allocations: u64,
 }
 
+/// Side length of the square tiles that `tick` tracks changes in.
+pub const TILE_SIZE: u32 = 32;
+
+/// The largest `k` accepted by `Universe::step_pow2`.
+pub const MAX_STEP_POW2: u32 = 60;
+
 // index![col, row]
 macro_rules! index {
     ($col:expr, $row:expr, $width:expr) => {
@@ -66,76 +168,209 @@ macro_rules! index {
     };
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Universe {
     pub fn new(ty: InitType) -> Universe {
-        let width = 64;
-        let height = 64;
+        Universe::with_size(64, 64, ty).expect("64x64 is a valid size")
+    }
 
+    pub fn with_size(width: u32, height: u32, ty: InitType) -> Result<Universe, UniverseError> {
+        UniverseError::check_size(width, height)?;
         let size = (width * height) as usize;
-        let mut cells = FixedBitSet::with_capacity(size);
-
-        match ty {
-            InitType::Random => Universe::init_random(width, height, &mut cells),
-            InitType::Clear => {}
-        }
+        let seed = random_seed();
 
-        Universe {
+        let mut universe = Universe {
             width,
             height,
-            cells,
+            cells: FixedBitSet::with_capacity(size),
+            next: FixedBitSet::default(),
+            changed: FixedBitSet::default(),
+            active: FixedBitSet::default(),
+            flipped: Vec::new(),
+            rows: Vec::new(),
+            words: Vec::new(),
+            rule: Rule::default(),
+            topology: Topology::default(),
+            seed,
+            rng: Pcg32::new(seed),
+            hashlife: None,
+            history: History::default(),
+            generation: 0,
+            series: Series::default(),
             perf: false,
+            #[cfg(feature = "simd")]
+            simd: true,
+            #[cfg(feature = "alloc-counter")]
+            tick_allocations: 0,
+        };
+        universe.touch_all();
+        match ty {
+            InitType::Random => universe.put_random(),
+            InitType::Clear => {}
         }
+        Ok(universe)
+    }
+
+    /// Creates a 64x64 universe filled from `seed`, where each cell is alive
+    /// with probability `density`. The same seed always gives the same soup.
+    pub fn new_seeded(seed: u64, density: f64) -> Universe {
+        let mut universe = Universe::new(InitType::Clear);
+        universe.set_seed(seed);
+        universe.put_random_region(0, 0, universe.width, universe.height, density);
+        universe
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Restarts the random number generator from `seed`.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.rng = Pcg32::new(seed);
     }
 
     pub fn set_perf(&mut self) {
         self.perf = true;
     }
 
-    fn init_random(width: u32, height: u32, cells: &mut FixedBitSet) {
-        let size = (width * height) as usize;
-        for i in 0..size {
-            cells.set(i, Math::random() < 0.5);
-        }
+    /// Switches `tick` between the SIMD backend (the default) and the plain
+    /// 64-bit one, to compare the two.
+    #[cfg(feature = "simd")]
+    pub fn set_simd(&mut self, enabled: bool) {
+        self.simd = enabled;
+    }
+
+    /// Number of heap allocations made by the last `tick`.
+    #[cfg(feature = "alloc-counter")]
+    pub fn tick_allocations(&self) -> u64 {
+        self.tick_allocations
+    }
+
+    /// Sets the rule from B/S notation, e.g. `B36/S23`.
+    pub fn set_rule_string(&mut self, rule: &str) -> Result<(), UniverseError> {
+        self.set_rule(rule.parse()?);
+        Ok(())
+    }
+
+    pub fn rule_string(&self) -> String {
+        self.rule.to_string()
+    }
+
+    /// Sets the topology and size from Golly's bounded grid notation, such
+    /// as `T64,64+5` or `K32*,16`. The cells are kept at the top left.
+    pub fn set_topology_string(&mut self, spec: &str) -> Result<(), UniverseError> {
+        let (topology, width, height) = Topology::parse_spec(spec)?;
+        self.topology = topology;
+        self.touch_all();
+        self.resize(width, height, Anchor::TopLeft)
+    }
+
+    pub fn topology_string(&self) -> String {
+        self.topology.spec(self.width, self.height)
     }
 
     pub fn clear(&mut self) {
-        let size = (self.width * self.height) as usize;
-        self.cells = FixedBitSet::with_capacity(size);
+        self.edit(|universe| {
+            universe.cells.clear();
+            universe.flipped.clear();
+            universe.touch_all();
+        });
     }
 
-    pub fn put_random(&mut self) {
-        let mut next = self.cells.clone();
-        Universe::init_random(self.width, self.height, &mut next);
+    /// Changes the dimensions of the universe, cropping or padding the
+    /// existing cells around `anchor`.
+    pub fn resize(&mut self, width: u32, height: u32, anchor: Anchor) -> Result<(), UniverseError> {
+        UniverseError::check_size(width, height)?;
+        self.topology.check(width, height)?;
+        let (offset_col, offset_row) = anchor.offset(self.width, self.height, width, height);
+        let mut next = FixedBitSet::with_capacity((width * height) as usize);
+        for row in 0..self.height {
+            let new_row = row as i64 + offset_row;
+            if new_row < 0 || new_row >= height as i64 {
+                continue;
+            }
+            for col in 0..self.width {
+                let new_col = col as i64 + offset_col;
+                if new_col < 0 || new_col >= width as i64 {
+                    continue;
+                }
+                let idx = self.get_index(row, col);
+                next.set(index![new_col, new_row, width as i64], self.cells[idx]);
+            }
+        }
+        self.width = width;
+        self.height = height;
         self.cells = next;
+        self.flipped.clear();
+        self.touch_all();
+        // Deltas only make sense between states of the same size.
+        self.history.clear();
+        Ok(())
+    }
+
+    pub fn put_random(&mut self) {
+        self.put_random_region(0, 0, self.width, self.height, 0.5);
+    }
+
+    /// Fills a `w` x `h` region with random cells, alive with probability
+    /// `density`. Parts of the region past an edge follow the topology.
+    pub fn put_random_region(&mut self, x: u32, y: u32, w: u32, h: u32, density: f64) {
+        self.edit(|universe| {
+            for row in y..y.saturating_add(h) {
+                for col in x..x.saturating_add(w) {
+                    let alive = universe.rng.chance(density);
+                    let (width, height) = (universe.width, universe.height);
+                    let cell = universe.topology.map(col as i64, row as i64, width, height);
+                    if let Some((col, row)) = cell {
+                        let idx = universe.get_index(row, col);
+                        universe.cells.set(idx, alive);
+                        universe.touch(row, col);
+                    }
+                }
+            }
+        });
     }
 
     fn put_points(&mut self, points: Vec<(u32, u32)>) {
-        let offset_row = 5;
-        let offset_col = 5;
-        let mut next = self.cells.clone();
-        for (x, y) in points {
-            next.set(index![x + offset_col, y + offset_row, self.width], true);
-        }
-        self.cells = next;
+        Pattern::new(points).place(self, 5, 5, Transform::Identity);
+    }
+
+    pub fn put_pattern(&mut self, pattern: &Pattern, col: i32, row: i32, transform: Transform) {
+        pattern.place(self, col, row, transform);
+    }
+
+    pub fn get_cell(&self, row: u32, col: u32) -> Result<Cell, UniverseError> {
+        let idx = self.checked_index(row, col)?;
+        Ok(Cell::from(self.cells[idx]))
+    }
+
+    pub fn set_cell(&mut self, row: u32, col: u32, cell: Cell) -> Result<(), UniverseError> {
+        self.set_cells(&[(row, col)], cell)
+    }
+
+    pub fn toggle_cell(&mut self, row: u32, col: u32) -> Result<(), UniverseError> {
+        let cell = match self.get_cell(row, col)? {
+            Cell::Dead => Cell::Alive,
+            Cell::Alive => Cell::Dead,
+        };
+        self.set_cell(row, col, cell)
+    }
+
+    /// Like `set_cells`, with the cells packed as `[row, col, row, col, ...]`
+    /// so JS can pass them in one `Uint32Array`. A trailing unpaired value is
+    /// ignored.
+    pub fn set_cells_packed(&mut self, cells: &[u32], cell: Cell) -> Result<(), UniverseError> {
+        let pairs: Vec<_> = cells.chunks_exact(2).map(|pair| (pair[0], pair[1])).collect();
+        self.set_cells(&pairs, cell)
     }
 
     pub fn put_glider(&mut self) {
-        self.put_points(vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        self.put_pattern(&Pattern::glider(), 5, 5, Transform::Identity);
     }
 
     pub fn put_spaceship(&mut self) {
-        self.put_points(vec![
-            (0, 0),
-            (3, 0),
-            (4, 1),
-            (0, 2),
-            (4, 2),
-            (1, 3),
-            (2, 3),
-            (3, 3),
-            (4, 3),
-        ]);
+        self.put_pattern(&Pattern::spaceship(), 5, 5, Transform::Identity);
     }
 
     pub fn put_line(&mut self) {
@@ -162,8 +397,51 @@ impl Universe {
         self.put_points(points);
     }
 
-    pub fn render(&self) -> String {
-        self.to_string()
+    /// Replaces the contents of the universe with an RLE pattern. The pattern
+    /// is centered, and the universe grows if the pattern does not fit.
+    pub fn load_rle(&mut self, input: &str) -> Result<(), UniverseError> {
+        let rle: Rle = input.parse()?;
+        let rule = rle.rule;
+        self.load_pattern(&rle.into())?;
+        if let Some(rule) = rule {
+            self.set_rule(rule);
+        }
+        Ok(())
+    }
+
+    pub fn to_rle(&self) -> String {
+        let rle = Rle {
+            width: self.width,
+            height: self.height,
+            rule: Some(self.rule),
+            cells: self.live_cells(),
+            ..Rle::default()
+        };
+        rle.to_string()
+    }
+
+    pub fn render(&self, options: &RenderOptions) -> String {
+        let mut out = String::new();
+        self.write_cells(&mut out, options)
+            .expect("writing to a String cannot fail");
+        out
+    }
+
+    /// Replaces the contents of the universe with a plaintext (`.cells`)
+    /// pattern, placed the same way as `load_rle`.
+    pub fn load_plaintext(&mut self, input: &str) -> Result<(), UniverseError> {
+        let pattern: Plaintext = input.parse()?;
+        self.load_pattern(&pattern.into())
+    }
+
+    pub fn to_plaintext(&self) -> String {
+        let pattern = Plaintext {
+            width: self.width,
+            height: self.height,
+            cells: self.live_cells(),
+            ..Plaintext::default()
+        };
+        pattern.to_string()
     }
 
     pub fn tick(&mut self) {
@@ -171,44 +449,319 @@ impl Universe {
         if self.perf {
             _timer = Timer::new("Universe::tick");
         }
-        let mut next = self.cells.clone();
+        #[cfg(feature = "alloc-counter")]
+        let allocations = alloc::allocations();
+
+        // Reuse the buffers of the previous tick, so a steady run does not
+        // allocate. Tiles that are not recomputed keep their cells.
+        let mut next = std::mem::take(&mut self.next);
+        if next.len() != self.cells.len() {
+            next = FixedBitSet::with_capacity(self.cells.len());
+        }
+        next.as_mut_slice().copy_from_slice(self.cells.as_slice());
 
+        self.mark_active();
+        let mut changed = std::mem::take(&mut self.changed);
+        changed.clear();
+        let mut rows = std::mem::take(&mut self.rows);
+        let mut words = std::mem::take(&mut self.words);
+        self.tick_interior(&mut next, &mut changed, &mut rows, &mut words);
+        self.rows = rows;
+        self.words = words;
+
+        // The border touches the edges, so it goes through the topology.
         for row in 0..self.height {
-            for col in 0..self.width {
+            let step = if row == 0 || row == self.height - 1 {
+                1
+            } else {
+                (self.width as usize - 1).max(1)
+            };
+            for col in (0..self.width).step_by(step) {
+                let tile = self.tile_index(row, col);
+                if !self.active[tile] {
+                    continue;
+                }
                 let idx = self.get_index(row, col);
                 let cell = self.cells[idx];
                 let live_neighbors = self.live_neighbor_count(row, col);
+                let next_cell = self.rule.next_state(cell, live_neighbors);
 
-                next.set(
-                    idx,
-                    match (cell, live_neighbors) {
-                        (true, x) if x < 2 => false,
-                        (true, 2) | (true, 3) => true,
-                        (true, x) if x > 3 => false,
-                        (false, 3) => true,
-                        (otherwise, _) => otherwise,
-                    },
-                );
+                next.set(idx, next_cell);
+                if next_cell != cell {
+                    changed.insert(tile);
+                }
             }
         }
-        self.cells = next;
+        self.changed = changed;
+        self.next = std::mem::replace(&mut self.cells, next);
+        self.record_flipped();
+        if self.history.is_enabled() {
+            let delta = Delta::from_indices(&self.flipped, self.cells.len());
+            self.history.record(delta, 1);
+        }
+        self.generation += 1;
+        if self.series.is_enabled() {
+            let (births, deaths) = self.births_and_deaths();
+            self.record_sample(births, deaths);
+        }
+
+        #[cfg(feature = "alloc-counter")]
+        {
+            self.tick_allocations = alloc::allocations() - allocations;
+        }
+    }
+
+    /// Advances `n` generations in one call, so callers such as a fast
+    /// forward button cross the wasm boundary once instead of `n` times.
+    pub fn step(&mut self, n: u32) -> StepStats {
+        let _timer;
+        if self.perf {
+            _timer = Timer::new("Universe::step");
+        }
+        let stopwatch = Stopwatch::start();
+
+        let mut stats = StepStats::default();
+        for _ in 0..n {
+            self.tick();
+            let (births, deaths) = self.births_and_deaths();
+            stats.births += births as u64;
+            stats.deaths += deaths as u64;
+        }
+        stats.population = self.population();
+        stats.elapsed_ms = stopwatch.elapsed_ms();
+        stats
+    }
+
+    /// Generations since the universe was created, counting `undo` as going
+    /// back.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Keeps up to `capacity` ticks, jumps and edits for `undo`. The default
+    /// of 0 turns the history off.
+    pub fn set_history_capacity(&mut self, capacity: u32) {
+        self.history.set_capacity(capacity as usize);
+    }
+
+    /// Takes back the last tick, jump or edit. Returns false if there is
+    /// nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.history.take_undo() {
+            Some(entry) => {
+                self.apply(&entry.delta);
+                self.generation -= entry.generations;
+                self.series.truncate_after(self.generation);
+                self.history.push_redo(entry);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replays the last undone step. Anything new done since the undo makes
+    /// this return false.
+    pub fn redo(&mut self) -> bool {
+        match self.history.take_redo() {
+            Some(entry) => {
+                self.apply(&entry.delta);
+                self.generation += entry.generations;
+                if entry.generations > 0 && self.series.is_enabled() {
+                    let (births, deaths) = self.births_and_deaths();
+                    self.record_sample(births, deaths);
+                }
+                self.history.push_undo(entry);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Undoes steps until `n` generations have been taken back or the history
+    /// runs out. Returns how many generations were taken back, which can be
+    /// more than `n` after a `step_pow2` jump.
+    pub fn rewind(&mut self, n: u32) -> u64 {
+        let start = self.generation;
+        while start - self.generation < n as u64 && self.undo() {}
+        start - self.generation
+    }
+
+    pub fn population(&self) -> u32 {
+        self.cells.count_ones(..) as u32
+    }
+
+    /// The smallest rectangle holding every live cell, ignoring how the
+    /// edges are joined.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let width = self.width as usize;
+        let blocks = self.cells.as_slice();
+        let mut bounds: Option<(usize, usize, usize, usize)> = None;
+        for row in 0..self.height as usize {
+            for start in (0..width).step_by(64) {
+                let len = (width - start).min(64);
+                let bits = kernel::read_bits(blocks, row * width + start, len);
+                if bits == 0 {
+                    continue;
+                }
+                let left = start + bits.trailing_zeros() as usize;
+                let right = start + 63 - bits.leading_zeros() as usize;
+                bounds = Some(match bounds {
+                    None => (left, row, right, row),
+                    Some((l, t, r, b)) => (l.min(left), t.min(row), r.max(right), b.max(row)),
+                });
+            }
+        }
+        bounds.map(|(left, top, right, bottom)| BoundingBox {
+            col: left as i32,
+            row: top as i32,
+            width: (right - left + 1) as u32,
+            height: (bottom - top + 1) as u32,
+        })
+    }
+
+    /// Keeps statistics for the last `capacity` generations reached by
+    /// `tick`, `step_pow2` or `redo`, read back with the `stats_*` methods.
+    /// After a jump, births and deaths are the net change over the jump. The
+    /// default of 0 turns them off.
+    pub fn set_stats_capacity(&mut self, capacity: u32) {
+        self.series.set_capacity(capacity as usize);
+    }
+
+    /// The generation each sample was taken after, oldest first. The other
+    /// `stats_*` methods return their values in the same order.
+    pub fn stats_generations(&self) -> Vec<u64> {
+        self.series.collect(|s| s.generation)
+    }
+
+    pub fn stats_population(&self) -> Vec<u32> {
+        self.series.collect(|s| s.population)
+    }
+
+    pub fn stats_births(&self) -> Vec<u32> {
+        self.series.collect(|s| s.births)
+    }
+
+    pub fn stats_deaths(&self) -> Vec<u32> {
+        self.series.collect(|s| s.deaths)
+    }
+
+    /// Bounding boxes as `col, row, width, height` quadruples, all zero when
+    /// nothing was alive.
+    pub fn stats_bounding_boxes(&self) -> Vec<u32> {
+        self.series
+            .collect(|s| s.bounding_box)
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Live cells as a fraction of all cells.
+    pub fn stats_density(&self) -> Vec<f32> {
+        self.series.collect(|s| s.density)
+    }
+
+    /// Advances the universe by `2^k` generations at once with the HashLife
+    /// engine. Results are memoized, so repeated or periodic patterns get
+    /// much cheaper than calling `tick` `2^k` times. Only available on a
+    /// plain torus.
+    pub fn step_pow2(&mut self, k: u32) -> Result<(), UniverseError> {
+        if k > MAX_STEP_POW2 {
+            return Err(UniverseError::StepTooLarge(k));
+        }
+        if self.topology != Topology::Torus {
+            return Err(UniverseError::UnsupportedTopology(self.topology));
+        }
+        let _timer;
+        if self.perf {
+            _timer = Timer::new("Universe::step_pow2");
+        }
+
+        let mut engine = match self.hashlife.take() {
+            Some(engine) if engine.rule() == self.rule && !engine.is_full() => engine,
+            _ => HashLife::new(self.rule),
+        };
+        let mut next = FixedBitSet::with_capacity((self.width * self.height) as usize);
+        let width = self.width;
+        engine.step_torus(
+            self.width,
+            self.height,
+            k as u8,
+            |col, row| self.cells[(row * width + col) as usize],
+            |col, row| next.insert((row * width + col) as usize),
+        );
+        let before = std::mem::replace(&mut self.cells, next);
+        if self.history.is_enabled() {
+            self.history.record(Delta::between(&before, &self.cells), 1 << k);
+        }
+        self.generation += 1 << k;
+        self.flipped.clear();
+        self.flipped.extend(before.symmetric_difference(&self.cells).map(|idx| idx as u32));
+        if self.series.is_enabled() {
+            let (births, deaths) = self.births_and_deaths();
+            self.record_sample(births, deaths);
+        }
+        self.touch_all();
+        self.hashlife = Some(engine);
+        Ok(())
     }
 
     fn get_index(&self, row: u32, column: u32) -> usize {
         (row * self.width + column) as usize
     }
 
+    // Clears the universe, growing it if needed, and centers the pattern.
+    fn load_pattern(&mut self, pattern: &Pattern) -> Result<(), UniverseError> {
+        let mut width = self.width.max(pattern.width());
+        let mut height = self.height.max(pattern.height());
+        if self.topology == Topology::Sphere {
+            width = width.max(height);
+            height = width;
+        }
+        if (width, height) != (self.width, self.height) {
+            UniverseError::check_size(width, height)?;
+            self.topology.check(width, height)?;
+            self.width = width;
+            self.height = height;
+            self.cells = FixedBitSet::with_capacity((width * height) as usize);
+            self.history.clear();
+        }
+
+        let col = (width - pattern.width()) as i64 / 2;
+        let row = (height - pattern.height()) as i64 / 2;
+        self.edit(|universe| {
+            universe.cells.clear();
+            universe.flipped.clear();
+            universe.touch_all();
+            let cells = pattern.cells().iter();
+            universe.put_cells(cells.map(|&(x, y)| (col + x as i64, row + y as i64)));
+        });
+        Ok(())
+    }
+
+    fn live_cells(&self) -> Vec<(u32, u32)> {
+        self.cells
+            .ones()
+            .map(|idx| (idx as u32 % self.width, idx as u32 / self.width))
+            .collect()
+    }
+
     fn live_neighbor_count(&self, row: u32, column: u32) -> u8 {
         let mut count = 0;
-        for delta_row in [self.height - 1, 0, 1].iter().cloned() {
-            for delta_col in [self.width - 1, 0, 1].iter().cloned() {
+        for delta_row in -1..=1 {
+            for delta_col in -1..=1 {
                 if delta_row == 0 && delta_col == 0 {
                     continue;
                 }
-                let neighbor_row = (row + delta_row) % self.height;
-                let neighbor_col = (column + delta_col) % self.width;
-                let idx = self.get_index(neighbor_row, neighbor_col);
-                count += self.cells[idx] as u8;
+                let neighbor = self.topology.map(
+                    column as i64 + delta_col,
+                    row as i64 + delta_row,
+                    self.width,
+                    self.height,
+                );
+                if let Some((neighbor_col, neighbor_row)) = neighbor {
+                    let idx = self.get_index(neighbor_row, neighbor_col);
+                    count += self.cells[idx] as u8;
+                }
             }
         }
         count
@@ -225,17 +778,356 @@ impl Universe {
     pub fn cells(&self) -> *const u32 {
         self.cells.as_slice().as_ptr()
     }
+
+    /// Number of `TILE_SIZE` tiles across the universe. The last column of
+    /// tiles may be cut off by the right edge.
+    pub fn tile_columns(&self) -> u32 {
+        self.width.div_ceil(TILE_SIZE)
+    }
+
+    pub fn tile_rows(&self) -> u32 {
+        self.height.div_ceil(TILE_SIZE)
+    }
+
+    /// Indices of the cells that flipped in the last `tick`, `step_pow2`,
+    /// `undo` or `redo`, in the layout of `cells`. There are
+    /// `changed_cells_len` of them, so a renderer can repaint just those.
+    /// Clearing, resizing or loading a pattern empties the list; other
+    /// edits are not included.
+    pub fn changed_cells(&self) -> *const u32 {
+        self.flipped.as_ptr()
+    }
+
+    pub fn changed_cells_len(&self) -> usize {
+        self.flipped.len()
+    }
+
+    /// Tiles that changed in the last `tick` or were edited since, as
+    /// `row * tile_columns + col` indices. Only these need to be redrawn.
+    pub fn changed_tiles(&self) -> Vec<u32> {
+        self.changed.ones().map(|tile| tile as u32).collect()
+    }
 }
 
-impl fmt::Display for Universe {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for line in self.cells.as_slice().chunks(self.width as usize) {
-            for &cell in line {
-                let symbol = if cell == 1 { '◻' } else { '◼' };
-                write!(f, "{}", symbol)?;
+impl Universe {
+    // Computes the cells of active tiles away from the edges 64 at a time,
+    // one row of words at a time, and marks the tiles that changed. Cells in
+    // the first and last row and column come out wrong and are left for
+    // `tick` to fix.
+    fn tick_interior(
+        &self,
+        next: &mut FixedBitSet,
+        changed: &mut FixedBitSet,
+        rows: &mut Vec<u64>,
+        words: &mut Vec<usize>,
+    ) {
+        if self.width < 3 || self.height < 3 {
+            return;
+        }
+        let width = self.width as usize;
+        let row_words = width.div_ceil(64);
+        let blocks = self.cells.as_slice();
+        let read_row = |row: usize, buf: &mut [u64]| {
+            for (i, word) in buf.iter_mut().enumerate() {
+                *word = kernel::read_bits(blocks, row * width + i * 64, width - i * 64);
+            }
+        };
+        // The bits of word `i` that are neither past the end of the row nor
+        // in the first or last column.
+        let inner = |i: usize| {
+            let len = (width - i * 64).min(64);
+            let mut bits = kernel::mask(len);
+            if i == 0 {
+                bits &= !1;
+            }
+            if i == row_words - 1 {
+                bits &= !(1 << (len - 1));
+            }
+            bits
+        };
+
+        rows.clear();
+        rows.resize(4 * row_words, 0);
+        let tile_cols = self.tile_columns() as usize;
+        let tile = TILE_SIZE as usize;
+        for band in 0..self.tile_rows() as usize {
+            // Word `i` holds tiles `2i` and `2i + 1` of the band.
+            let first_tile = band * tile_cols;
+            words.clear();
+            words.extend((0..row_words).filter(|&i| {
+                let active = |t: usize| t < tile_cols && self.active[first_tile + t];
+                active(2 * i) || active(2 * i + 1)
+            }));
+            let first = (band * tile).max(1);
+            let last = ((band + 1) * tile).min(self.height as usize - 1);
+            if words.is_empty() || first >= last {
+                continue;
+            }
+
+            let (mut above, rest) = rows.split_at_mut(row_words);
+            let (mut current, rest) = rest.split_at_mut(row_words);
+            let (mut below, out) = rest.split_at_mut(row_words);
+            read_row(first - 1, above);
+            read_row(first, current);
+            for row in first..last {
+                read_row(row + 1, below);
+                self.next_words(above, current, below, words, out);
+                for &i in words.iter() {
+                    // The first and last columns are left to the border
+                    // pass, which skips them when their tile is idle.
+                    let bits = (out[i] & inner(i)) | (current[i] & !inner(i));
+                    let start = row * width + i * 64;
+                    kernel::write_bits(next.as_mut_slice(), start, width - i * 64, bits);
+                    let diff = current[i] ^ bits;
+                    if diff as u32 != 0 {
+                        changed.insert(first_tile + 2 * i);
+                    }
+                    if diff >> 32 != 0 {
+                        changed.insert(first_tile + 2 * i + 1);
+                    }
+                }
+                std::mem::swap(&mut above, &mut current);
+                std::mem::swap(&mut current, &mut below);
+            }
+        }
+    }
+
+    // Collects the cells that differ between `cells` and the previous
+    // generation in `next`. Only changed tiles can hold any.
+    fn record_flipped(&mut self) {
+        // The list keeps its capacity, so it only grows when more cells flip
+        // than in any earlier tick. Start with room for one tile's worth, so
+        // small boards never grow it again.
+        self.flipped.clear();
+        let room = self.cells.len().min((TILE_SIZE * TILE_SIZE) as usize);
+        if self.flipped.capacity() < room {
+            self.flipped.reserve_exact(room);
+        }
+
+        let (width, height) = (self.width as usize, self.height as usize);
+        let (cols, tile) = (self.tile_columns() as usize, TILE_SIZE as usize);
+        let (old, new) = (self.next.as_slice(), self.cells.as_slice());
+        for index in self.changed.ones() {
+            let (x, y) = (index % cols * tile, index / cols * tile);
+            let len = (width - x).min(tile);
+            for row in y..(y + tile).min(height) {
+                let start = row * width + x;
+                let mut bits =
+                    kernel::read_bits(old, start, len) ^ kernel::read_bits(new, start, len);
+                while bits != 0 {
+                    self.flipped.push((start + bits.trailing_zeros() as usize) as u32);
+                    bits &= bits - 1;
+                }
             }
-            write!(f, "\n")?;
         }
+    }
+
+    fn tile_index(&self, row: u32, col: u32) -> usize {
+        ((row / TILE_SIZE) * self.tile_columns() + col / TILE_SIZE) as usize
+    }
+
+    // Marks the tile holding a cell as changed, so the next `tick`
+    // recomputes it and its neighbors.
+    fn touch(&mut self, row: u32, col: u32) {
+        let tile = self.tile_index(row, col);
+        self.changed.insert(tile);
+    }
+
+    fn touch_all(&mut self) {
+        let tiles = (self.tile_columns() * self.tile_rows()) as usize;
+        if self.changed.len() != tiles {
+            self.changed = FixedBitSet::with_capacity(tiles);
+        }
+        self.changed.insert_range(..);
+    }
+
+    // Activates every changed tile and its neighbors. Cells on the edges can
+    // see each other through the topology, so a change in any edge tile
+    // activates all of them.
+    fn mark_active(&mut self) {
+        let (cols, rows) = (self.tile_columns(), self.tile_rows());
+        let tiles = (cols * rows) as usize;
+        if self.active.len() == tiles {
+            self.active.clear();
+        } else {
+            self.active = FixedBitSet::with_capacity(tiles);
+        }
+
+        let mut edge = false;
+        for tile in self.changed.ones() {
+            let (x, y) = (tile as u32 % cols, tile as u32 / cols);
+            edge |= x == 0 || y == 0 || x == cols - 1 || y == rows - 1;
+            for y in y.saturating_sub(1)..=(y + 1).min(rows - 1) {
+                for x in x.saturating_sub(1)..=(x + 1).min(cols - 1) {
+                    self.active.insert((y * cols + x) as usize);
+                }
+            }
+        }
+        if edge {
+            for x in 0..cols {
+                self.active.insert(x as usize);
+                self.active.insert(((rows - 1) * cols + x) as usize);
+            }
+            for y in 0..rows {
+                self.active.insert((y * cols) as usize);
+                self.active.insert((y * cols + cols - 1) as usize);
+            }
+        }
+    }
+
+    #[cfg(feature = "simd")]
+    fn next_words(
+        &self,
+        above: &[u64],
+        row: &[u64],
+        below: &[u64],
+        words: &[usize],
+        out: &mut [u64],
+    ) {
+        if self.simd {
+            kernel::next_words::<simd::Wide>(self.rule, above, row, below, words, out);
+        } else {
+            kernel::next_words::<u64>(self.rule, above, row, below, words, out);
+        }
+    }
+
+    #[cfg(not(feature = "simd"))]
+    fn next_words(
+        &self,
+        above: &[u64],
+        row: &[u64],
+        below: &[u64],
+        words: &[usize],
+        out: &mut [u64],
+    ) {
+        kernel::next_words::<u64>(self.rule, above, row, below, words, out);
+    }
+
+    // Sets cells alive, mapping coordinates past the edges through the
+    // topology like `live_neighbor_count` does.
+    pub(crate) fn set_alive(&mut self, cells: impl Iterator<Item = (i64, i64)>) {
+        self.edit(|universe| universe.put_cells(cells));
+    }
+
+    fn put_cells(&mut self, cells: impl Iterator<Item = (i64, i64)>) {
+        for (col, row) in cells {
+            if let Some((col, row)) = self.topology.map(col, row, self.width, self.height) {
+                let idx = self.get_index(row, col);
+                self.cells.set(idx, true);
+                self.touch(row, col);
+            }
+        }
+    }
+
+    // Runs `f` and records what it changed as one undoable edit.
+    fn edit(&mut self, f: impl FnOnce(&mut Universe)) {
+        let before = self.history.is_enabled().then(|| self.cells.clone());
+        f(self);
+        if let Some(before) = before {
+            self.history.record(Delta::between(&before, &self.cells), 0);
+        }
+    }
+
+    // Flips the cells of `delta` and reports them like a tick would.
+    fn apply(&mut self, delta: &Delta) {
+        let flipped = &mut self.flipped;
+        flipped.clear();
+        delta.apply(&mut self.cells, |idx| flipped.push(idx));
+        for i in 0..self.flipped.len() {
+            let idx = self.flipped[i];
+            self.touch(idx / self.width, idx % self.width);
+        }
+    }
+
+    // How many of the cells flipped by the last tick were born and how many
+    // died.
+    fn births_and_deaths(&self) -> (u32, u32) {
+        let births = self
+            .flipped
+            .iter()
+            .filter(|&&idx| self.cells[idx as usize])
+            .count() as u32;
+        (births, self.flipped.len() as u32 - births)
+    }
+
+    fn record_sample(&mut self, births: u32, deaths: u32) {
+        let population = self.population();
+        let bounding_box = self.bounding_box().map_or([0; 4], |b| {
+            [b.col as u32, b.row as u32, b.width, b.height]
+        });
+        self.series.push(Sample {
+            generation: self.generation,
+            population,
+            births,
+            deaths,
+            bounding_box,
+            density: population as f32 / self.cells.len() as f32,
+        });
+    }
+
+    fn write_cells(&self, out: &mut impl fmt::Write, options: &RenderOptions) -> fmt::Result {
+        render::write_cells(out, options, self.width, self.height, |row, col| {
+            self.cells[self.get_index(row, col)]
+        })
+    }
+
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// Sets every `(row, col)` in `cells` to `cell`, as one undoable edit.
+    /// Nothing changes if any of them is out of bounds.
+    pub fn set_cells(&mut self, cells: &[(u32, u32)], cell: Cell) -> Result<(), UniverseError> {
+        for &(row, col) in cells {
+            self.checked_index(row, col)?;
+        }
+        self.edit(|universe| {
+            for &(row, col) in cells {
+                let idx = universe.get_index(row, col);
+                universe.cells.set(idx, cell == Cell::Alive);
+                universe.touch(row, col);
+            }
+        });
+        Ok(())
+    }
+
+    fn checked_index(&self, row: u32, col: u32) -> Result<usize, UniverseError> {
+        if row < self.height && col < self.width {
+            Ok(self.get_index(row, col))
+        } else {
+            Err(UniverseError::OutOfBounds {
+                row,
+                col,
+                width: self.width,
+                height: self.height,
+            })
+        }
+    }
+
+    /// The cells that flipped in the last step; see `changed_cells`.
+    pub fn changed_cell_indices(&self) -> &[u32] {
+        &self.flipped
+    }
+
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+        self.touch_all();
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn set_topology(&mut self, topology: Topology) -> Result<(), UniverseError> {
+        topology.check(self.width, self.height)?;
+        self.topology = topology;
+        self.touch_all();
         Ok(())
     }
 }
+
+impl fmt::Display for Universe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.write_cells(f, &RenderOptions::default())
+    }
+}
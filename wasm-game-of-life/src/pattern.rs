@@ -0,0 +1,211 @@
+use crate::apgcode::Apgcode;
+use crate::census;
+use crate::error::UniverseError;
+use crate::plaintext::Plaintext;
+use crate::rle::Rle;
+use crate::rule::Rule;
+use crate::Universe;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// One of the 8 symmetries of a square. Rotations are clockwise.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Transform {
+    #[default]
+    Identity = 0,
+    Rotate90 = 1,
+    Rotate180 = 2,
+    Rotate270 = 3,
+    /// Mirrors columns, left becomes right.
+    FlipHorizontal = 4,
+    /// Mirrors rows, top becomes bottom.
+    FlipVertical = 5,
+    /// Swaps rows and columns.
+    FlipDiagonal = 6,
+    /// Swaps rows and columns across the other diagonal.
+    FlipAntiDiagonal = 7,
+}
+
+impl Transform {
+    pub const ALL: [Transform; 8] = [
+        Transform::Identity,
+        Transform::Rotate90,
+        Transform::Rotate180,
+        Transform::Rotate270,
+        Transform::FlipHorizontal,
+        Transform::FlipVertical,
+        Transform::FlipDiagonal,
+        Transform::FlipAntiDiagonal,
+    ];
+
+    fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Transform::Rotate90
+                | Transform::Rotate270
+                | Transform::FlipDiagonal
+                | Transform::FlipAntiDiagonal
+        )
+    }
+
+    // Maps `(col, row)` inside a `width` x `height` box into the transformed box.
+    fn apply(self, (col, row): (u32, u32), width: u32, height: u32) -> (u32, u32) {
+        let (right, bottom) = (width - 1 - col, height - 1 - row);
+        match self {
+            Transform::Identity => (col, row),
+            Transform::Rotate90 => (bottom, col),
+            Transform::Rotate180 => (right, bottom),
+            Transform::Rotate270 => (row, right),
+            Transform::FlipHorizontal => (right, row),
+            Transform::FlipVertical => (col, bottom),
+            Transform::FlipDiagonal => (row, col),
+            Transform::FlipAntiDiagonal => (bottom, right),
+        }
+    }
+}
+
+/// A set of live cells inside a `width` x `height` bounding box.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Pattern {
+    width: u32,
+    height: u32,
+    cells: Vec<(u32, u32)>,
+}
+
+impl Pattern {
+    /// Builds a pattern from `(col, row)` pairs; the bounding box is the
+    /// smallest one that starts at the origin and holds every cell.
+    pub fn new(cells: impl IntoIterator<Item = (u32, u32)>) -> Pattern {
+        let cells: Vec<_> = cells.into_iter().collect();
+        let width = cells.iter().map(|&(col, _)| col + 1).max().unwrap_or(0);
+        let height = cells.iter().map(|&(_, row)| row + 1).max().unwrap_or(0);
+        Pattern {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn cells(&self) -> &[(u32, u32)] {
+        &self.cells
+    }
+
+    /// Runs the pattern on its own under `rule` to find its apgcode. Returns
+    /// `None` if it dies or has not repeated after `max_generations`.
+    pub fn to_apgcode(
+        &self,
+        rule: Rule,
+        max_generations: u32,
+    ) -> Result<Option<Apgcode>, UniverseError> {
+        let (classification, phases) = census::classify(self, rule, max_generations)?;
+        Ok(Apgcode::canonical(classification, &phases))
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Pattern {
+    pub fn glider() -> Pattern {
+        Pattern::new(vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)])
+    }
+
+    pub fn spaceship() -> Pattern {
+        Pattern::new(vec![
+            (0, 0),
+            (3, 0),
+            (4, 1),
+            (0, 2),
+            (4, 2),
+            (1, 3),
+            (2, 3),
+            (3, 3),
+            (4, 3),
+        ])
+    }
+
+    pub fn from_rle(input: &str) -> Result<Pattern, UniverseError> {
+        let rle: Rle = input.parse()?;
+        Ok(rle.into())
+    }
+
+    pub fn from_plaintext(input: &str) -> Result<Pattern, UniverseError> {
+        let pattern: Plaintext = input.parse()?;
+        Ok(pattern.into())
+    }
+
+    /// Reads an `xs`, `xp` or `xq` apgcode, such as `xq4_153`.
+    pub fn from_apgcode(input: &str) -> Result<Pattern, UniverseError> {
+        let code: Apgcode = input.parse()?;
+        Ok(code.into())
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn population(&self) -> u32 {
+        self.cells.len() as u32
+    }
+
+    pub fn transformed(&self, transform: Transform) -> Pattern {
+        let (width, height) = if transform.swaps_axes() {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        };
+        Pattern {
+            width,
+            height,
+            cells: self
+                .cells
+                .iter()
+                .map(|&cell| transform.apply(cell, self.width, self.height))
+                .collect(),
+        }
+    }
+
+    /// Sets the cells of the transformed pattern alive, with its top-left
+    /// corner at `(col, row)`. Cells past an edge follow the topology of the
+    /// universe, so they are dropped on a bounded grid.
+    pub fn place(&self, universe: &mut Universe, col: i32, row: i32, transform: Transform) {
+        let pattern = self.transformed(transform);
+        universe.set_alive(
+            pattern
+                .cells
+                .iter()
+                .map(|&(x, y)| (col as i64 + x as i64, row as i64 + y as i64)),
+        );
+    }
+}
+
+impl From<Rle> for Pattern {
+    fn from(rle: Rle) -> Self {
+        Pattern {
+            width: rle.width,
+            height: rle.height,
+            cells: rle.cells,
+        }
+    }
+}
+
+impl From<Plaintext> for Pattern {
+    fn from(pattern: Plaintext) -> Self {
+        Pattern {
+            width: pattern.width,
+            height: pattern.height,
+            cells: pattern.cells,
+        }
+    }
+}
+
+impl From<Apgcode> for Pattern {
+    fn from(code: Apgcode) -> Self {
+        Pattern::new(code.cells)
+    }
+}
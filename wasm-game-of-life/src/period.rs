@@ -0,0 +1,116 @@
+use crate::kernel;
+use crate::Universe;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::Hasher;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// A cycle found by `PeriodDetector`.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Periodicity {
+    /// Generations between repeats; 1 for a still life.
+    pub period: u64,
+    /// The first generation that is part of the cycle.
+    pub start: u64,
+    /// How far the pattern moves right and down every period, zero unless
+    /// it is a spaceship.
+    pub dx: i32,
+    pub dy: i32,
+}
+
+/// Watches a universe tick by tick and notices when it repeats an earlier
+/// generation, up to translation.
+///
+/// Each generation is hashed relative to the bounding box of its live cells,
+/// so a spaceship counts as repeating once it has the same shape again. The
+/// bounding box does not follow a pattern across the edge of a torus, so a
+/// spaceship that wraps around is only caught once it is back where it
+/// started.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Debug, Default)]
+pub struct PeriodDetector {
+    // Hash of a generation to the generation and the corner of its bounding
+    // box when it was first seen.
+    seen: HashMap<u64, (u64, i32, i32)>,
+    last: Option<u64>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl PeriodDetector {
+    pub fn new() -> PeriodDetector {
+        PeriodDetector::default()
+    }
+
+    /// Forgets every generation seen so far. Needed after editing the cells
+    /// without going back in time; going back is noticed on its own.
+    pub fn reset(&mut self) {
+        self.seen.clear();
+        self.last = None;
+    }
+
+    /// Records the current generation of `universe`, returning the cycle if
+    /// it was seen before.
+    pub fn observe(&mut self, universe: &Universe) -> Option<Periodicity> {
+        let generation = universe.generation();
+        if self.last.is_some_and(|last| generation <= last) {
+            self.reset();
+        }
+        self.last = Some(generation);
+
+        let (hash, col, row) = normalized_hash(universe);
+        let (start, start_col, start_row) =
+            *self.seen.entry(hash).or_insert((generation, col, row));
+        if start == generation {
+            return None;
+        }
+        Some(Periodicity {
+            period: generation - start,
+            start,
+            dx: col - start_col,
+            dy: row - start_row,
+        })
+    }
+}
+
+/// Hashes the live cells relative to their bounding box, returning the hash
+/// and the top-left corner of the box.
+fn normalized_hash(universe: &Universe) -> (u64, i32, i32) {
+    let mut hasher = DefaultHasher::new();
+    let bounds = match universe.bounding_box() {
+        Some(bounds) => bounds,
+        None => return (hasher.finish(), 0, 0),
+    };
+    hasher.write_u32(bounds.width);
+    hasher.write_u32(bounds.height);
+
+    let width = universe.width() as usize;
+    let blocks = universe.cells.as_slice();
+    for row in bounds.row as usize..(bounds.row as u32 + bounds.height) as usize {
+        let start = row * width + bounds.col as usize;
+        for offset in (0..bounds.width as usize).step_by(64) {
+            let len = (bounds.width as usize - offset).min(64);
+            hasher.write_u64(kernel::read_bits(blocks, start + offset, len));
+        }
+    }
+    (hasher.finish(), bounds.col, bounds.row)
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl Universe {
+    /// Ticks until the universe repeats an earlier generation, giving up
+    /// after `max_generations`. The current generation counts as the first
+    /// one seen.
+    pub fn run_until_periodic(&mut self, max_generations: u32) -> Option<Periodicity> {
+        let mut detector = PeriodDetector::new();
+        detector.observe(self);
+        for _ in 0..max_generations {
+            self.tick();
+            if let Some(periodicity) = detector.observe(self) {
+                return Some(periodicity);
+            }
+        }
+        None
+    }
+}
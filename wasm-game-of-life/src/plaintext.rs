@@ -0,0 +1,80 @@
+use crate::error::ParseError;
+use std::fmt;
+use std::str::FromStr;
+
+/// A pattern in the plaintext (`.cells`) format: `!` comment lines followed by
+/// rows of `.` (dead) and `O` (alive).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Plaintext {
+    pub name: Option<String>,
+    pub comments: Vec<String>,
+    pub width: u32,
+    pub height: u32,
+    /// Live cells as `(col, row)` pairs.
+    pub cells: Vec<(u32, u32)>,
+}
+
+impl FromStr for Plaintext {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut pattern = Plaintext::default();
+        let mut row = 0;
+
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim_end();
+            if let Some(comment) = line.strip_prefix('!') {
+                match comment.strip_prefix("Name:") {
+                    Some(name) => pattern.name = Some(name.trim().to_string()),
+                    None => pattern.comments.push(comment.trim().to_string()),
+                }
+                continue;
+            }
+
+            let mut width = 0;
+            for (col, c) in line.chars().enumerate() {
+                match c {
+                    '.' => {}
+                    'O' | '*' => pattern.cells.push((col as u32, row)),
+                    c => {
+                        return Err(ParseError::new(
+                            i + 1,
+                            col + 1,
+                            format!("unexpected character {:?}", c),
+                        ))
+                    }
+                }
+                width += 1;
+            }
+            pattern.width = pattern.width.max(width);
+            row += 1;
+        }
+        pattern.height = row;
+        Ok(pattern)
+    }
+}
+
+impl fmt::Display for Plaintext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(name) = &self.name {
+            writeln!(f, "!Name: {}", name)?;
+        }
+        for comment in &self.comments {
+            writeln!(f, "!{}", comment)?;
+        }
+
+        // Grow the grid for cells outside `width` and `height` rather than
+        // losing them.
+        let cells = self.cells.iter();
+        let width = cells.clone().map(|&(col, _)| col + 1).fold(self.width, u32::max);
+        let height = cells.map(|&(_, row)| row + 1).fold(self.height, u32::max);
+        let mut rows = vec![vec!['.'; width as usize]; height as usize];
+        for &(col, row) in &self.cells {
+            rows[row as usize][col as usize] = 'O';
+        }
+        for row in rows {
+            writeln!(f, "{}", row.into_iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+// Everything that talks to the browser lives here. Native builds, including
+// `cargo test` with the `wasm` feature enabled, use std instead.
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+mod imp {
+    use js_sys::{Date, Math};
+    use web_sys::console;
+
+    pub struct Timer<'a> {
+        name: &'a str,
+    }
+
+    impl<'a> Timer<'a> {
+        pub fn new(name: &'a str) -> Timer<'a> {
+            console::time_with_label(name);
+            Timer { name }
+        }
+    }
+
+    impl<'a> Drop for Timer<'a> {
+        fn drop(&mut self) {
+            console::time_end_with_label(self.name);
+        }
+    }
+
+    pub(crate) struct Stopwatch {
+        start: f64,
+    }
+
+    impl Stopwatch {
+        pub(crate) fn start() -> Stopwatch {
+            Stopwatch { start: Date::now() }
+        }
+
+        pub(crate) fn elapsed_ms(&self) -> f64 {
+            Date::now() - self.start
+        }
+    }
+
+    pub(crate) fn random_seed() -> u64 {
+        let high = (Math::random() * 4294967296.0) as u64;
+        let low = (Math::random() * 4294967296.0) as u64;
+        high << 32 | low
+    }
+}
+
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+mod imp {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+    use std::time::Instant;
+
+    pub struct Timer<'a> {
+        name: &'a str,
+        start: Instant,
+    }
+
+    impl<'a> Timer<'a> {
+        pub fn new(name: &'a str) -> Timer<'a> {
+            Timer {
+                name,
+                start: Instant::now(),
+            }
+        }
+    }
+
+    impl<'a> Drop for Timer<'a> {
+        fn drop(&mut self) {
+            eprintln!("{}: {:?}", self.name, self.start.elapsed());
+        }
+    }
+
+    pub(crate) struct Stopwatch {
+        start: Instant,
+    }
+
+    impl Stopwatch {
+        pub(crate) fn start() -> Stopwatch {
+            Stopwatch {
+                start: Instant::now(),
+            }
+        }
+
+        pub(crate) fn elapsed_ms(&self) -> f64 {
+            self.start.elapsed().as_secs_f64() * 1000.0
+        }
+    }
+
+    pub(crate) fn random_seed() -> u64 {
+        RandomState::new().build_hasher().finish()
+    }
+}
+
+pub use imp::Timer;
+pub(crate) use imp::{random_seed, Stopwatch};
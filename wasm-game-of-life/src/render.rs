@@ -0,0 +1,142 @@
+use std::fmt;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// A rectangle of cells, in universe coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Viewport {
+    pub col: u32,
+    pub row: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Viewport {
+    fn clip(self, width: u32, height: u32) -> Viewport {
+        let col = self.col.min(width);
+        let row = self.row.min(height);
+        Viewport {
+            col,
+            row,
+            width: self.width.min(width - col),
+            height: self.height.min(height - row),
+        }
+    }
+}
+
+/// Controls how `Universe::render` prints cells.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RenderOptions {
+    alive: char,
+    dead: char,
+    coordinates: bool,
+    viewport: Option<Viewport>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            alive: '◻',
+            dead: '◼',
+            coordinates: false,
+            viewport: None,
+        }
+    }
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl RenderOptions {
+    pub fn new() -> RenderOptions {
+        RenderOptions::default()
+    }
+
+    pub fn set_alive(&mut self, alive: char) {
+        self.alive = alive;
+    }
+
+    pub fn set_dead(&mut self, dead: char) {
+        self.dead = dead;
+    }
+
+    /// Prefixes each row with its index and adds a header with the last
+    /// digit of each column index.
+    pub fn set_coordinates(&mut self, coordinates: bool) {
+        self.coordinates = coordinates;
+    }
+
+    /// Only renders the given rectangle, clipped to the universe.
+    pub fn set_viewport(&mut self, col: u32, row: u32, width: u32, height: u32) {
+        self.viewport = Some(Viewport {
+            col,
+            row,
+            width,
+            height,
+        });
+    }
+
+    pub fn clear_viewport(&mut self) {
+        self.viewport = None;
+    }
+}
+
+impl RenderOptions {
+    pub fn alive(&self) -> char {
+        self.alive
+    }
+
+    pub fn dead(&self) -> char {
+        self.dead
+    }
+
+    pub fn coordinates(&self) -> bool {
+        self.coordinates
+    }
+
+    pub fn viewport(&self) -> Option<Viewport> {
+        self.viewport
+    }
+}
+
+// Writes the cells of a `width` x `height` grid, one line per row.
+pub(crate) fn write_cells(
+    out: &mut impl fmt::Write,
+    options: &RenderOptions,
+    width: u32,
+    height: u32,
+    is_alive: impl Fn(u32, u32) -> bool,
+) -> fmt::Result {
+    let full = Viewport {
+        col: 0,
+        row: 0,
+        width,
+        height,
+    };
+    let view = options.viewport.unwrap_or(full).clip(width, height);
+    let cols = view.col..view.col + view.width;
+    let rows = view.row..view.row + view.height;
+    let label_width = rows.end.saturating_sub(1).to_string().len();
+
+    if options.coordinates {
+        write!(out, "{:w$} ", "", w = label_width)?;
+        for col in cols.clone() {
+            write!(out, "{}", col % 10)?;
+        }
+        writeln!(out)?;
+    }
+    for row in rows {
+        if options.coordinates {
+            write!(out, "{:>w$} ", row, w = label_width)?;
+        }
+        for col in cols.clone() {
+            let symbol = if is_alive(row, col) {
+                options.alive
+            } else {
+                options.dead
+            };
+            write!(out, "{}", symbol)?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,215 @@
+use crate::error::ParseError;
+use crate::rule::Rule;
+use std::fmt;
+use std::str::FromStr;
+
+const LINE_WIDTH: usize = 70;
+
+/// A pattern in Run Length Encoded format, as found on LifeWiki.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Rle {
+    pub name: Option<String>,
+    pub comments: Vec<String>,
+    pub width: u32,
+    pub height: u32,
+    pub rule: Option<Rule>,
+    /// Live cells as `(col, row)` pairs.
+    pub cells: Vec<(u32, u32)>,
+}
+
+fn column(line: &str, byte: usize) -> usize {
+    line[..byte].chars().count() + 1
+}
+
+fn parse_header(line: &str, line_no: usize, rle: &mut Rle) -> Result<(), ParseError> {
+    let mut width = None;
+    let mut height = None;
+    let mut part_start = 0;
+    for part in line.split(',') {
+        let leading = part.len() - part.trim_start().len();
+        let eq = part.find('=').ok_or_else(|| {
+            ParseError::new(line_no, column(line, part_start + leading), "expected `key = value`")
+        })?;
+        let key = part[..eq].trim();
+        let raw = &part[eq + 1..];
+        let value = raw.trim();
+        let value_column = column(line, part_start + eq + 1 + raw.len() - raw.trim_start().len());
+        part_start += part.len() + 1;
+
+        match key {
+            "x" | "y" => {
+                let n = value.parse::<u32>().map_err(|_| {
+                    ParseError::new(line_no, value_column, format!("invalid size {:?}", value))
+                })?;
+                if key == "x" {
+                    width = Some(n);
+                } else {
+                    height = Some(n);
+                }
+            }
+            "rule" => {
+                // Golly appends the grid topology after a colon, e.g. `B3/S23:T64,64`.
+                let rule = value.split(':').next().unwrap_or_default();
+                rle.rule = Some(
+                    rule.parse()
+                        .map_err(|err| ParseError::new(line_no, value_column, format!("{}", err)))?,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    match (width, height) {
+        (Some(width), Some(height)) => {
+            rle.width = width;
+            rle.height = height;
+            Ok(())
+        }
+        _ => Err(ParseError::new(line_no, 1, "header must define both `x` and `y`")),
+    }
+}
+
+impl FromStr for Rle {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut rle = Rle::default();
+        let mut header = false;
+        let (mut col, mut row) = (0u32, 0u32);
+        let mut count: Option<u32> = None;
+
+        for (i, line) in input.lines().enumerate() {
+            let line_no = i + 1;
+            if !header {
+                let trimmed = line.trim();
+                if trimmed.is_empty() {
+                    continue;
+                }
+                if let Some(comment) = trimmed.strip_prefix('#') {
+                    let text = comment.get(1..).unwrap_or_default().trim().to_string();
+                    match comment.chars().next() {
+                        Some('N') => rle.name = Some(text),
+                        Some('C') | Some('c') => rle.comments.push(text),
+                        _ => {}
+                    }
+                    continue;
+                }
+                parse_header(line, line_no, &mut rle)?;
+                header = true;
+                continue;
+            }
+
+            for (byte, c) in line.char_indices() {
+                let error =
+                    |message: String| Err(ParseError::new(line_no, column(line, byte), message));
+                let n = count.unwrap_or(1);
+                match c {
+                    '0'..='9' => {
+                        let digit = c.to_digit(10).unwrap();
+                        match count.unwrap_or(0).checked_mul(10).and_then(|n| n.checked_add(digit)) {
+                            Some(n) => count = Some(n),
+                            None => return error("run count is too large".to_string()),
+                        }
+                        continue;
+                    }
+                    'b' | '.' => col = col.saturating_add(n),
+                    'o' => {
+                        if col.saturating_add(n) > rle.width || row >= rle.height {
+                            return error(format!(
+                                "cells run past the {}x{} bounding box",
+                                rle.width, rle.height
+                            ));
+                        }
+                        rle.cells.extend((col..col + n).map(|x| (x, row)));
+                        col += n;
+                    }
+                    '$' => {
+                        row = row.saturating_add(n);
+                        col = 0;
+                    }
+                    '!' => return Ok(rle),
+                    c if c.is_whitespace() => {
+                        if count.is_some() {
+                            return error("expected a tag after the run count".to_string());
+                        }
+                        continue;
+                    }
+                    c => return error(format!("unexpected character {:?}", c)),
+                }
+                count = None;
+            }
+        }
+
+        if !header {
+            let line = input.lines().count().max(1);
+            return Err(ParseError::new(line, 1, "missing `x = .., y = ..` header"));
+        }
+        Ok(rle)
+    }
+}
+
+struct Writer<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    line_len: usize,
+}
+
+impl<'a, 'b> Writer<'a, 'b> {
+    fn token(&mut self, n: u32, tag: char) -> fmt::Result {
+        let token = if n == 1 {
+            tag.to_string()
+        } else {
+            format!("{}{}", n, tag)
+        };
+        if self.line_len + token.len() > LINE_WIDTH {
+            writeln!(self.f)?;
+            self.line_len = 0;
+        }
+        self.line_len += token.len();
+        write!(self.f, "{}", token)
+    }
+}
+
+impl fmt::Display for Rle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(name) = &self.name {
+            writeln!(f, "#N {}", name)?;
+        }
+        for comment in &self.comments {
+            writeln!(f, "#C {}", comment)?;
+        }
+        writeln!(
+            f,
+            "x = {}, y = {}, rule = {}",
+            self.width,
+            self.height,
+            self.rule.unwrap_or_default()
+        )?;
+
+        let mut cells = self.cells.clone();
+        cells.sort_by_key(|&(col, row)| (row, col));
+        cells.dedup();
+
+        let mut w = Writer { f, line_len: 0 };
+        let (mut col, mut row) = (0, 0);
+        let mut cells = cells.into_iter().peekable();
+        while let Some((x, y)) = cells.next() {
+            if y > row {
+                w.token(y - row, '$')?;
+                row = y;
+                col = 0;
+            }
+            if x > col {
+                w.token(x - col, 'b')?;
+            }
+            let mut end = x + 1;
+            while cells.peek() == Some(&(end, y)) {
+                cells.next();
+                end += 1;
+            }
+            w.token(end - x, 'o')?;
+            col = end;
+        }
+        w.token(1, '!')?;
+        writeln!(w.f)
+    }
+}
@@ -0,0 +1,39 @@
+/// A small PCG32 (XSH RR) generator. It is fast, has no dependencies and
+/// produces the same sequence for the same seed on every platform.
+#[derive(Clone, Debug)]
+pub struct Pcg32 {
+    state: u64,
+    inc: u64,
+}
+
+const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+const INCREMENT: u64 = 1_442_695_040_888_963_407;
+
+impl Pcg32 {
+    pub fn new(seed: u64) -> Pcg32 {
+        let mut rng = Pcg32 {
+            state: 0,
+            inc: INCREMENT,
+        };
+        rng.next_u32();
+        rng.state = rng.state.wrapping_add(seed);
+        rng.next_u32();
+        rng
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        let old = self.state;
+        self.state = old.wrapping_mul(MULTIPLIER).wrapping_add(self.inc);
+        let xorshifted = (((old >> 18) ^ old) >> 27) as u32;
+        xorshifted.rotate_right((old >> 59) as u32)
+    }
+
+    /// Returns a number uniformly distributed in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        self.next_u32() as f64 / (1u64 << 32) as f64
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+}
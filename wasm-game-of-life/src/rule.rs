@@ -0,0 +1,153 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A Life-like cellular automaton rule, e.g. `B3/S23`.
+///
+/// Bit `n` of `birth` (resp. `survival`) is set when a dead (resp. live)
+/// cell with `n` live neighbors is alive in the next generation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rule {
+    birth: u16,
+    survival: u16,
+}
+
+impl Rule {
+    pub const CONWAY: Rule = Rule {
+        birth: 1 << 3,
+        survival: 1 << 2 | 1 << 3,
+    };
+
+    pub fn new(birth: &[u8], survival: &[u8]) -> Result<Rule, RuleParseError> {
+        Ok(Rule {
+            birth: mask(birth)?,
+            survival: mask(survival)?,
+        })
+    }
+
+    pub fn next_state(&self, alive: bool, live_neighbors: u8) -> bool {
+        let mask = if alive { self.survival } else { self.birth };
+        mask & (1 << live_neighbors) != 0
+    }
+
+    pub fn birth(&self) -> u16 {
+        self.birth
+    }
+
+    pub fn survival(&self) -> u16 {
+        self.survival
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Rule::CONWAY
+    }
+}
+
+fn mask(counts: &[u8]) -> Result<u16, RuleParseError> {
+    let mut mask = 0;
+    for &n in counts {
+        if n > 8 {
+            return Err(RuleParseError::InvalidCount(n));
+        }
+        mask |= 1 << n;
+    }
+    Ok(mask)
+}
+
+fn write_counts(f: &mut fmt::Formatter, mask: u16) -> fmt::Result {
+    for n in 0..=8 {
+        if mask & (1 << n) != 0 {
+            write!(f, "{}", n)?;
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "B")?;
+        write_counts(f, self.birth)?;
+        write!(f, "/S")?;
+        write_counts(f, self.survival)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RuleParseError {
+    /// The rule has no `/` between birth and survival parts.
+    MissingSeparator,
+    /// The parts are not prefixed with `B`/`S`, or the prefixes are mixed up.
+    InvalidPrefix(String),
+    /// A neighbor count is not a digit.
+    InvalidDigit(char),
+    /// A neighbor count is larger than 8.
+    InvalidCount(u8),
+    /// The same neighbor count appears twice in one part.
+    DuplicateCount(u8),
+}
+
+impl fmt::Display for RuleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleParseError::MissingSeparator => write!(f, "rule is missing a '/' separator"),
+            RuleParseError::InvalidPrefix(rule) => write!(f, "invalid rule prefixes in {:?}", rule),
+            RuleParseError::InvalidDigit(c) => write!(f, "invalid neighbor count {:?}", c),
+            RuleParseError::InvalidCount(n) => write!(f, "neighbor count {} is out of range", n),
+            RuleParseError::DuplicateCount(n) => write!(f, "neighbor count {} is repeated", n),
+        }
+    }
+}
+
+impl Error for RuleParseError {}
+
+fn parse_counts(digits: &str) -> Result<u16, RuleParseError> {
+    let mut mask = 0;
+    for c in digits.chars() {
+        let n = c.to_digit(10).ok_or(RuleParseError::InvalidDigit(c))? as u8;
+        if n > 8 {
+            return Err(RuleParseError::InvalidCount(n));
+        }
+        if mask & (1 << n) != 0 {
+            return Err(RuleParseError::DuplicateCount(n));
+        }
+        mask |= 1 << n;
+    }
+    Ok(mask)
+}
+
+fn strip_prefix(part: &str, prefix: char) -> Option<&str> {
+    part.strip_prefix(prefix)
+        .or_else(|| part.strip_prefix(prefix.to_ascii_lowercase()))
+}
+
+impl FromStr for Rule {
+    type Err = RuleParseError;
+
+    // Accepts `B3/S23`, `S23/B3` and the older survival/birth form `23/3`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (first, second) = s.split_once('/').ok_or(RuleParseError::MissingSeparator)?;
+
+        let (birth, survival) = if let (Some(birth), Some(survival)) =
+            (strip_prefix(first, 'B'), strip_prefix(second, 'S'))
+        {
+            (birth, survival)
+        } else if let (Some(survival), Some(birth)) =
+            (strip_prefix(first, 'S'), strip_prefix(second, 'B'))
+        {
+            (birth, survival)
+        } else if !first.starts_with(char::is_alphabetic) && !second.starts_with(char::is_alphabetic)
+        {
+            (second, first)
+        } else {
+            return Err(RuleParseError::InvalidPrefix(s.to_string()));
+        };
+
+        Ok(Rule {
+            birth: parse_counts(birth)?,
+            survival: parse_counts(survival)?,
+        })
+    }
+}
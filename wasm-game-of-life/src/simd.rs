@@ -0,0 +1,191 @@
+// 128-cell lanes for `kernel::next_words`.
+
+use crate::kernel::Lanes;
+use std::ops::{BitAnd, BitOr, BitXor, Not, Shl, Shr};
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod imp {
+    use super::*;
+    use core::arch::wasm32::*;
+
+    #[derive(Clone, Copy)]
+    pub(crate) struct Wide(v128);
+
+    impl BitAnd for Wide {
+        type Output = Wide;
+
+        fn bitand(self, rhs: Wide) -> Wide {
+            Wide(v128_and(self.0, rhs.0))
+        }
+    }
+
+    impl BitOr for Wide {
+        type Output = Wide;
+
+        fn bitor(self, rhs: Wide) -> Wide {
+            Wide(v128_or(self.0, rhs.0))
+        }
+    }
+
+    impl BitXor for Wide {
+        type Output = Wide;
+
+        fn bitxor(self, rhs: Wide) -> Wide {
+            Wide(v128_xor(self.0, rhs.0))
+        }
+    }
+
+    impl Not for Wide {
+        type Output = Wide;
+
+        fn not(self) -> Wide {
+            Wide(v128_not(self.0))
+        }
+    }
+
+    impl Shl<u32> for Wide {
+        type Output = Wide;
+
+        fn shl(self, n: u32) -> Wide {
+            Wide(u64x2_shl(self.0, n))
+        }
+    }
+
+    impl Shr<u32> for Wide {
+        type Output = Wide;
+
+        fn shr(self, n: u32) -> Wide {
+            Wide(u64x2_shr(self.0, n))
+        }
+    }
+
+    impl Lanes for Wide {
+        const WORDS: usize = 2;
+
+        fn zero() -> Self {
+            Wide(u64x2(0, 0))
+        }
+
+        fn from_fn(f: impl Fn(usize) -> u64) -> Self {
+            Wide(u64x2(f(0), f(1)))
+        }
+
+        fn load(words: &[u64]) -> Self {
+            match words {
+                // SAFETY: there are two words to read, and `v128_load` does
+                // not need them to be aligned.
+                [_, _, ..] => Wide(unsafe { v128_load(words.as_ptr() as *const v128) }),
+                [word] => Wide(u64x2(*word, 0)),
+                [] => Wide(u64x2(0, 0)),
+            }
+        }
+
+        fn store(self, out: &mut [u64]) {
+            match out {
+                // SAFETY: there is room for two words, and `v128_store` does
+                // not need them to be aligned.
+                [_, _, ..] => unsafe { v128_store(out.as_mut_ptr() as *mut v128, self.0) },
+                [word] => *word = u64x2_extract_lane::<0>(self.0),
+                [] => {}
+            }
+        }
+
+        fn for_each_word(self, mut f: impl FnMut(usize, u64)) {
+            f(0, u64x2_extract_lane::<0>(self.0));
+            f(1, u64x2_extract_lane::<1>(self.0));
+        }
+    }
+}
+
+// Without `simd128` the lanes are two plain words, which keeps the SIMD code
+// path testable natively.
+#[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
+mod imp {
+    use super::*;
+
+    #[derive(Clone, Copy)]
+    pub(crate) struct Wide([u64; 2]);
+
+    impl Wide {
+        fn zip(self, rhs: Wide, f: impl Fn(u64, u64) -> u64) -> Wide {
+            Wide([f(self.0[0], rhs.0[0]), f(self.0[1], rhs.0[1])])
+        }
+    }
+
+    impl BitAnd for Wide {
+        type Output = Wide;
+
+        fn bitand(self, rhs: Wide) -> Wide {
+            self.zip(rhs, |a, b| a & b)
+        }
+    }
+
+    impl BitOr for Wide {
+        type Output = Wide;
+
+        fn bitor(self, rhs: Wide) -> Wide {
+            self.zip(rhs, |a, b| a | b)
+        }
+    }
+
+    impl BitXor for Wide {
+        type Output = Wide;
+
+        fn bitxor(self, rhs: Wide) -> Wide {
+            self.zip(rhs, |a, b| a ^ b)
+        }
+    }
+
+    impl Not for Wide {
+        type Output = Wide;
+
+        fn not(self) -> Wide {
+            Wide([!self.0[0], !self.0[1]])
+        }
+    }
+
+    impl Shl<u32> for Wide {
+        type Output = Wide;
+
+        fn shl(self, n: u32) -> Wide {
+            Wide([self.0[0] << n, self.0[1] << n])
+        }
+    }
+
+    impl Shr<u32> for Wide {
+        type Output = Wide;
+
+        fn shr(self, n: u32) -> Wide {
+            Wide([self.0[0] >> n, self.0[1] >> n])
+        }
+    }
+
+    impl Lanes for Wide {
+        const WORDS: usize = 2;
+
+        fn zero() -> Self {
+            Wide([0; 2])
+        }
+
+        fn from_fn(f: impl Fn(usize) -> u64) -> Self {
+            Wide([f(0), f(1)])
+        }
+
+        fn load(words: &[u64]) -> Self {
+            Wide::from_fn(|k| words.get(k).copied().unwrap_or(0))
+        }
+
+        fn store(self, out: &mut [u64]) {
+            for (word, &lane) in out.iter_mut().zip(&self.0) {
+                *word = lane;
+            }
+        }
+
+        fn for_each_word(self, mut f: impl FnMut(usize, u64)) {
+            f(0, self.0[0]);
+            f(1, self.0[1]);
+        }
+    }
+}
+
+pub(crate) use imp::Wide;
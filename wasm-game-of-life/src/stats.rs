@@ -0,0 +1,78 @@
+use std::collections::VecDeque;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+// The most samples reserved up front; larger series grow as they fill.
+const MAX_RESERVED: usize = 1 << 16;
+
+/// What happened during a call to `Universe::step`.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StepStats {
+    /// Live cells after the last generation.
+    pub population: u32,
+    /// Cells born, summed over every generation.
+    pub births: u64,
+    /// Cells that died, summed over every generation.
+    pub deaths: u64,
+    /// Wall-clock time spent, in milliseconds.
+    pub elapsed_ms: f64,
+}
+
+/// The statistics of one generation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Sample {
+    pub(crate) generation: u64,
+    pub(crate) population: u32,
+    pub(crate) births: u32,
+    pub(crate) deaths: u32,
+    /// `[col, row, width, height]`, all zero when nothing is alive.
+    pub(crate) bounding_box: [u32; 4],
+    pub(crate) density: f32,
+}
+
+/// The samples of the last `capacity` generations. Like the undo history it
+/// is off until it gets a capacity.
+#[derive(Default)]
+pub(crate) struct Series {
+    capacity: usize,
+    samples: VecDeque<Sample>,
+}
+
+impl Series {
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    pub(crate) fn set_capacity(&mut self, capacity: usize) {
+        while self.samples.len() > capacity {
+            self.samples.pop_front();
+        }
+        // Reserve up front so recording rarely allocates.
+        let reserved = capacity.min(MAX_RESERVED);
+        self.samples.reserve_exact(reserved.saturating_sub(self.samples.len()));
+        self.capacity = capacity;
+    }
+
+    pub(crate) fn push(&mut self, sample: Sample) {
+        if !self.is_enabled() {
+            return;
+        }
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Drops the samples of generations after `generation`, after an undo.
+    pub(crate) fn truncate_after(&mut self, generation: u64) {
+        while self.samples.back().is_some_and(|s| s.generation > generation) {
+            self.samples.pop_back();
+        }
+    }
+
+    /// Collects one field of every sample, oldest first.
+    pub(crate) fn collect<T>(&self, field: impl Fn(&Sample) -> T) -> Vec<T> {
+        self.samples.iter().map(field).collect()
+    }
+}
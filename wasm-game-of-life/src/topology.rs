@@ -0,0 +1,203 @@
+use crate::error::UniverseError;
+use std::fmt;
+
+/// How the edges of a universe are joined.
+///
+/// The string form used by `Universe::set_topology_string` follows Golly's
+/// bounded grid notation, e.g. `P64,64`, `T64,64+5`, `K64*,64`, `C64,64` or
+/// `S64`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Topology {
+    /// Everything past the edges is permanently dead.
+    Bounded,
+    #[default]
+    Torus,
+    /// A torus whose edges are joined with an offset. Leaving through the
+    /// bottom edge re-enters at the top `col_shift` columns to the right, and
+    /// leaving through the right edge re-enters at the left `row_shift` rows
+    /// down. At most one of the shifts may be non-zero.
+    ShiftedTorus { col_shift: i32, row_shift: i32 },
+    /// A torus with one pair of edges joined with a twist. By default the
+    /// columns are mirrored when crossing the top or bottom edge; with
+    /// `flip_rows` the rows are mirrored when crossing the left or right edge.
+    KleinBottle { flip_rows: bool },
+    /// Both pairs of edges are joined with a twist.
+    CrossSurface,
+    /// The top edge is joined to the left edge and the bottom edge to the
+    /// right edge. Needs a square universe.
+    Sphere,
+}
+
+enum Suffix {
+    None,
+    Twist,
+    Shift(i32),
+}
+
+fn parse_dimension(part: &str) -> Option<(u32, Suffix)> {
+    let end = part.find(|c: char| !c.is_ascii_digit()).unwrap_or(part.len());
+    let size = part[..end].parse().ok()?;
+    let suffix = match &part[end..] {
+        "" => Suffix::None,
+        "*" => Suffix::Twist,
+        shift if shift.starts_with('+') || shift.starts_with('-') => {
+            Suffix::Shift(shift.parse().ok()?)
+        }
+        _ => return None,
+    };
+    Some((size, suffix))
+}
+
+impl Topology {
+    /// Parses a Golly bounded grid such as `T64,64+5` into the topology and
+    /// the size of the grid.
+    pub(crate) fn parse_spec(spec: &str) -> Result<(Topology, u32, u32), UniverseError> {
+        let invalid = || UniverseError::InvalidTopology(format!("cannot parse {:?}", spec));
+        let mut chars = spec.chars();
+        let kind = chars.next().ok_or_else(invalid)?.to_ascii_uppercase();
+        let mut parts = chars.as_str().split(',');
+        let (width, x) = parse_dimension(parts.next().unwrap_or_default()).ok_or_else(invalid)?;
+        let (height, y) = match parts.next() {
+            Some(part) => parse_dimension(part).ok_or_else(invalid)?,
+            None if kind == 'S' => (width, Suffix::None),
+            None => return Err(invalid()),
+        };
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+
+        let topology = match (kind, x, y) {
+            ('P', Suffix::None, Suffix::None) => Topology::Bounded,
+            ('T', Suffix::None, Suffix::None) => Topology::Torus,
+            ('T', Suffix::Shift(col_shift), Suffix::None) => Topology::ShiftedTorus {
+                col_shift,
+                row_shift: 0,
+            },
+            ('T', Suffix::None, Suffix::Shift(row_shift)) => Topology::ShiftedTorus {
+                col_shift: 0,
+                row_shift,
+            },
+            ('K', Suffix::Twist, Suffix::None) => Topology::KleinBottle { flip_rows: false },
+            ('K', Suffix::None, Suffix::Twist) => Topology::KleinBottle { flip_rows: true },
+            ('C', Suffix::None, Suffix::None) => Topology::CrossSurface,
+            ('S', Suffix::None, Suffix::None) => Topology::Sphere,
+            _ => return Err(invalid()),
+        };
+        UniverseError::check_size(width, height)?;
+        topology.check(width, height)?;
+        Ok((topology, width, height))
+    }
+
+    /// Formats the topology of a `width` x `height` grid in Golly's notation.
+    pub(crate) fn spec(self, width: u32, height: u32) -> String {
+        match self {
+            Topology::Bounded => format!("P{},{}", width, height),
+            Topology::Torus => format!("T{},{}", width, height),
+            Topology::ShiftedTorus { col_shift, .. } if col_shift != 0 => {
+                format!("T{}{:+},{}", width, col_shift, height)
+            }
+            Topology::ShiftedTorus { row_shift, .. } => {
+                format!("T{},{}{:+}", width, height, row_shift)
+            }
+            Topology::KleinBottle { flip_rows: false } => format!("K{}*,{}", width, height),
+            Topology::KleinBottle { flip_rows: true } => format!("K{},{}*", width, height),
+            Topology::CrossSurface => format!("C{},{}", width, height),
+            Topology::Sphere => format!("S{}", width),
+        }
+    }
+
+    pub(crate) fn check(self, width: u32, height: u32) -> Result<(), UniverseError> {
+        match self {
+            Topology::ShiftedTorus {
+                col_shift,
+                row_shift,
+            } if col_shift != 0 && row_shift != 0 => Err(UniverseError::InvalidTopology(
+                "a torus can only be shifted along one axis".to_string(),
+            )),
+            Topology::Sphere if width != height => Err(UniverseError::InvalidTopology(format!(
+                "a sphere needs a square universe, not {}x{}",
+                width, height
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Maps `(col, row)`, which may lie outside the `width` x `height` grid,
+    /// to the cell it stands for. Returns `None` for points that are not part
+    /// of the surface: anything past the edge of a bounded grid, and points
+    /// diagonally past a corner of a sphere or cross-surface.
+    pub(crate) fn map(self, col: i64, row: i64, width: u32, height: u32) -> Option<(u32, u32)> {
+        let (w, h) = (width as i64, height as i64);
+        let inside_col = (0..w).contains(&col);
+        let inside_row = (0..h).contains(&row);
+        if inside_col && inside_row {
+            return Some((col as u32, row as u32));
+        }
+
+        let (col, row) = match self {
+            Topology::Bounded => return None,
+            Topology::Torus => (col.rem_euclid(w), row.rem_euclid(h)),
+            Topology::ShiftedTorus {
+                col_shift,
+                row_shift,
+            } => {
+                let col = col + row.div_euclid(h) * col_shift as i64;
+                let row = row + col.div_euclid(w) * row_shift as i64;
+                (col.rem_euclid(w), row.rem_euclid(h))
+            }
+            Topology::KleinBottle { flip_rows: false } => {
+                let col = col.rem_euclid(w);
+                let flip = row.div_euclid(h) % 2 != 0;
+                (if flip { w - 1 - col } else { col }, row.rem_euclid(h))
+            }
+            Topology::KleinBottle { flip_rows: true } => {
+                let row = row.rem_euclid(h);
+                let flip = col.div_euclid(w) % 2 != 0;
+                (col.rem_euclid(w), if flip { h - 1 - row } else { row })
+            }
+            _ if !inside_col && !inside_row => return None,
+            Topology::CrossSurface if !inside_row => {
+                let flip = row.div_euclid(h) % 2 != 0;
+                (if flip { w - 1 - col } else { col }, row.rem_euclid(h))
+            }
+            Topology::CrossSurface => {
+                let flip = col.div_euclid(w) % 2 != 0;
+                (col.rem_euclid(w), if flip { h - 1 - row } else { row })
+            }
+            Topology::Sphere => {
+                // Crossing an edge turns the point a quarter around the
+                // corner joining it to its neighboring edge. Far away points
+                // take a few turns.
+                let n = w;
+                let (mut col, mut row) = (col, row);
+                while !((0..n).contains(&col) && (0..n).contains(&row)) {
+                    (col, row) = if row < 0 {
+                        (-1 - row, col)
+                    } else if col < 0 {
+                        (row, -1 - col)
+                    } else if row >= n {
+                        (2 * n - 1 - row, col)
+                    } else {
+                        (row, 2 * n - 1 - col)
+                    };
+                }
+                (col, row)
+            }
+        };
+        Some((col as u32, row as u32))
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Topology::Bounded => "bounded plane",
+            Topology::Torus => "torus",
+            Topology::ShiftedTorus { .. } => "shifted torus",
+            Topology::KleinBottle { .. } => "Klein bottle",
+            Topology::CrossSurface => "cross-surface",
+            Topology::Sphere => "sphere",
+        };
+        f.write_str(name)
+    }
+}
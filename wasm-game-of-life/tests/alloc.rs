@@ -0,0 +1,39 @@
+// Run with `cargo test --features alloc-counter`.
+#![cfg(feature = "alloc-counter")]
+
+use wasm_game_of_life::{InitType, Universe};
+
+#[test]
+fn steady_ticks_do_not_allocate() {
+    for (width, height) in [(64, 64), (200, 37), (2, 5)] {
+        let mut universe = Universe::with_size(width, height, InitType::Random).unwrap();
+        // The first tick sets up the second buffer.
+        universe.tick();
+        for _ in 0..10 {
+            universe.tick();
+            assert_eq!(universe.tick_allocations(), 0, "{}x{}", width, height);
+        }
+    }
+}
+
+#[test]
+fn resizing_reallocates_once() {
+    let mut universe = Universe::with_size(32, 32, InitType::Random).unwrap();
+    universe.tick();
+    universe.resize(48, 40, Default::default()).unwrap();
+    universe.tick();
+    assert!(universe.tick_allocations() > 0);
+    universe.tick();
+    assert_eq!(universe.tick_allocations(), 0);
+}
+
+#[test]
+fn recording_stats_does_not_allocate() {
+    let mut universe = Universe::with_size(64, 64, InitType::Random).unwrap();
+    universe.set_stats_capacity(4);
+    universe.tick();
+    for _ in 0..10 {
+        universe.tick();
+        assert_eq!(universe.tick_allocations(), 0);
+    }
+}
@@ -0,0 +1,106 @@
+use wasm_game_of_life::{Apgcode, ApgcodeKind, Pattern, Rule, Transform};
+
+const PULSAR: &str = "\
+..OOO...OOO..
+.............
+O....O.O....O
+O....O.O....O
+O....O.O....O
+..OOO...OOO..
+.............
+..OOO...OOO..
+O....O.O....O
+O....O.O....O
+O....O.O....O
+.............
+..OOO...OOO..";
+
+fn apgcode(plaintext: &str) -> String {
+    Pattern::from_plaintext(plaintext)
+        .unwrap()
+        .to_apgcode(Rule::CONWAY, 100)
+        .unwrap()
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn names_common_objects() {
+    let cases = [
+        ("OO\nOO", "xs4_33"),
+        (".OO.\nO..O\n.OO.", "xs6_696"),
+        ("OO.\nO.O\n.O.", "xs5_253"),
+        (".O.\nO.O\n.O.", "xs4_252"),
+        ("OOO", "xp2_7"),
+        (".O.\n..O\nOOO", "xq4_153"),
+        (".O..O\nO....\nO...O\nOOOO.", "xq4_6frc"),
+        (PULSAR, "xp3_co9nas0san9oczgoldlo0oldlogz1047210127401"),
+    ];
+    for &(plaintext, code) in &cases {
+        assert_eq!(apgcode(plaintext), code);
+    }
+}
+
+#[test]
+fn same_code_in_every_orientation_and_phase() {
+    let glider = Pattern::glider();
+    for &transform in &Transform::ALL {
+        let code = glider.transformed(transform).to_apgcode(Rule::CONWAY, 100).unwrap();
+        assert_eq!(code.unwrap().to_string(), "xq4_153");
+    }
+    // A later phase of the glider.
+    assert_eq!(apgcode("O.O\n.OO\n.O."), "xq4_153");
+}
+
+#[test]
+fn parses_codes() {
+    let code: Apgcode = "xq4_153".parse().unwrap();
+    assert_eq!(code.kind, ApgcodeKind::Spaceship { period: 4 });
+    let mut cells = Pattern::from(code).cells().to_vec();
+    cells.sort_unstable_by_key(|&(col, row)| (row, col));
+    assert_eq!(cells, Pattern::from_plaintext("OOO\n..O\n.O.").unwrap().cells());
+
+    // Runs of empty columns and a second strip.
+    let pattern = Pattern::from_apgcode("xs4_1w1y01z1").unwrap();
+    assert_eq!(pattern.cells(), &[(0, 0), (3, 0), (8, 0), (0, 5)][..]);
+
+    for code in [
+        "xs4_33",
+        "xp3_co9nas0san9oczgoldlo0oldlogz1047210127401",
+        "xq4_6frc",
+    ] {
+        let parsed: Apgcode = code.parse().unwrap();
+        assert_eq!(parsed.to_string(), code);
+    }
+}
+
+#[test]
+fn writes_long_runs_of_empty_columns() {
+    let code = Apgcode {
+        kind: ApgcodeKind::StillLife { population: 2 },
+        cells: vec![(0, 0), (45, 0)],
+    };
+    assert_eq!(code.to_string(), "xs2_1yzy11");
+    assert_eq!(code.to_string().parse::<Apgcode>().unwrap(), code);
+}
+
+#[test]
+fn rejects_malformed_codes() {
+    for (input, column) in [
+        ("33", 3),
+        ("xs_33", 1),
+        ("xz4_33", 1),
+        ("xs5_33", 1),
+        ("xs4_3!3", 6),
+        ("xs4_y", 5),
+    ] {
+        let err = input.parse::<Apgcode>().unwrap_err();
+        assert_eq!((err.line, err.column), (1, column), "{}", input);
+    }
+}
+
+#[test]
+fn dying_patterns_have_no_code() {
+    let lone = Pattern::new(vec![(0, 0)]);
+    assert_eq!(lone.to_apgcode(Rule::CONWAY, 10).unwrap(), None);
+}
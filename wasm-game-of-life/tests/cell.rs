@@ -0,0 +1,45 @@
+use wasm_game_of_life::{Cell, InitType, Universe, UniverseError};
+
+#[test]
+fn reads_and_writes_single_cells() {
+    let mut universe = Universe::with_size(6, 4, InitType::Clear).unwrap();
+    assert_eq!(universe.get_cell(1, 5), Ok(Cell::Dead));
+
+    universe.set_cell(1, 5, Cell::Alive).unwrap();
+    assert_eq!(universe.get_cell(1, 5), Ok(Cell::Alive));
+    assert_eq!(universe.to_plaintext(), "......\n.....O\n......\n......\n");
+
+    universe.toggle_cell(1, 5).unwrap();
+    universe.toggle_cell(3, 0).unwrap();
+    assert_eq!(universe.get_cell(1, 5), Ok(Cell::Dead));
+    assert_eq!(universe.get_cell(3, 0), Ok(Cell::Alive));
+}
+
+#[test]
+fn rejects_out_of_bounds_cells() {
+    let mut universe = Universe::with_size(6, 4, InitType::Clear).unwrap();
+    let err = UniverseError::OutOfBounds {
+        row: 4,
+        col: 0,
+        width: 6,
+        height: 4,
+    };
+    assert_eq!(universe.get_cell(4, 0), Err(err.clone()));
+    assert_eq!(universe.toggle_cell(4, 0), Err(err.clone()));
+    assert_eq!(universe.set_cells(&[(0, 0), (4, 0)], Cell::Alive), Err(err));
+    assert_eq!(universe.population(), 0);
+}
+
+#[test]
+fn bulk_edits_are_one_undo_step() {
+    let mut universe = Universe::with_size(6, 4, InitType::Clear).unwrap();
+    universe.set_history_capacity(8);
+    universe.set_cells(&[(0, 0), (0, 1), (0, 2)], Cell::Alive).unwrap();
+    universe.set_cells_packed(&[0, 1, 3, 3], Cell::Dead).unwrap();
+    assert_eq!(universe.to_plaintext(), "O.O...\n......\n......\n......\n");
+
+    assert!(universe.undo());
+    assert_eq!(universe.population(), 3);
+    assert!(universe.undo());
+    assert_eq!(universe.population(), 0);
+}
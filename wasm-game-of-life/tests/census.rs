@@ -0,0 +1,149 @@
+use wasm_game_of_life::{Census, Classification, InitType, Pattern, Transform, Universe};
+
+const BLOCK: &str = "OO\nOO";
+const BLINKER: &str = "OOO";
+const GLIDER: &str = ".O.\n..O\nOOO";
+const LWSS: &str = ".O..O\nO....\nO...O\nOOOO.";
+const BEEHIVE: &str = ".OO.\nO..O\n.OO.";
+
+fn universe_with(objects: &[(&str, i32, i32, Transform)]) -> Universe {
+    let mut universe = Universe::with_size(64, 64, InitType::Clear).unwrap();
+    for &(pattern, col, row, transform) in objects {
+        Pattern::from_plaintext(pattern)
+            .unwrap()
+            .place(&mut universe, col, row, transform);
+    }
+    universe
+}
+
+fn census_of(universe: &Universe, distance: u32) -> Vec<(u32, Classification)> {
+    let mut census = Census::new(distance, 64);
+    census.add(universe).unwrap();
+    census
+        .entries()
+        .iter()
+        .map(|entry| (entry.count, entry.classification))
+        .collect()
+}
+
+#[test]
+fn splits_objects_by_distance() {
+    // The blocks are two columns apart, with one empty column between them.
+    let universe = universe_with(&[
+        (BLOCK, 2, 2, Transform::Identity),
+        (BLOCK, 5, 2, Transform::Identity),
+        (BLINKER, 20, 20, Transform::Identity),
+    ]);
+    assert_eq!(universe.objects(1).len(), 3);
+    let objects = universe.objects(2);
+    assert_eq!(objects.len(), 2);
+    assert_eq!((objects[0].col, objects[0].row), (2, 2));
+    assert_eq!(objects[0].pattern.width(), 5);
+    assert_eq!(objects[0].pattern.population(), 8);
+}
+
+#[test]
+fn follows_objects_across_the_edge() {
+    let universe = universe_with(&[(BLOCK, 63, 63, Transform::Identity)]);
+    let objects = universe.objects(1);
+    assert_eq!(objects.len(), 1);
+    assert_eq!(objects[0].pattern, Pattern::from_plaintext(BLOCK).unwrap());
+}
+
+#[test]
+fn classifies_objects() {
+    let universe = universe_with(&[
+        (BLOCK, 2, 2, Transform::Identity),
+        (BLINKER, 10, 2, Transform::Identity),
+        (GLIDER, 20, 2, Transform::Identity),
+        (LWSS, 30, 2, Transform::Identity),
+    ]);
+    // One cell of the spaceship only touches the rest at distance 2.
+    assert_eq!(census_of(&universe, 1).len(), 5);
+    let mut census = census_of(&universe, 2);
+    census.sort_by_key(|&(_, classification)| format!("{:?}", classification));
+    assert_eq!(
+        census,
+        vec![
+            (1, Classification::Oscillator { period: 2 }),
+            (
+                1,
+                Classification::Spaceship {
+                    period: 4,
+                    dx: 1,
+                    dy: 1,
+                }
+            ),
+            (
+                1,
+                Classification::Spaceship {
+                    period: 4,
+                    dx: 2,
+                    dy: 0,
+                }
+            ),
+            (1, Classification::StillLife),
+        ]
+    );
+}
+
+#[test]
+fn counts_every_phase_and_orientation_together() {
+    let mut objects = Vec::new();
+    for (i, &transform) in Transform::ALL.iter().enumerate() {
+        let offset = 8 * i as i32;
+        objects.push((GLIDER, offset, 2, transform));
+        objects.push((BEEHIVE, offset, 10, transform));
+    }
+    let mut universe = universe_with(&objects);
+    // Leave the gliders in another phase.
+    universe.tick();
+
+    let mut census = Census::new(1, 64);
+    census.add(&universe).unwrap();
+    let entries = census.entries();
+    assert_eq!(entries.len(), 2);
+    assert!(entries.iter().all(|entry| entry.count == 8));
+    assert_eq!(census.count("xs6_696"), 8);
+    assert_eq!(census.count("xq4_153"), 8);
+    assert_eq!(census.total(), 16);
+}
+
+#[test]
+fn dying_objects_are_unknown() {
+    let universe = universe_with(&[("OO", 2, 2, Transform::Identity)]);
+    assert_eq!(census_of(&universe, 1), vec![(1, Classification::Unknown)]);
+    let mut census = Census::new(1, 64);
+    census.add(&universe).unwrap();
+    assert_eq!(census.count("zz_3"), 1);
+}
+
+#[test]
+fn classifies_again_under_another_rule() {
+    let mut universe = universe_with(&[(BLINKER, 2, 2, Transform::Identity)]);
+    let mut census = Census::new(1, 64);
+    census.add(&universe).unwrap();
+
+    // Under B3/S012345678 the blinker grows into a still life.
+    universe.set_rule_string("B3/S012345678").unwrap();
+    census.add(&universe).unwrap();
+    assert_eq!(census.count("xp2_7"), 1);
+    assert_eq!(census.count("xs29_8uuvuu8z011311"), 1);
+}
+
+#[test]
+fn counts_settled_soups() {
+    let mut census = Census::new(1, 256);
+    let mut settled = 0;
+    for seed in 0..4 {
+        let mut universe = Universe::with_size(32, 32, InitType::Clear).unwrap();
+        universe.set_seed(seed);
+        universe.put_random_region(8, 8, 16, 16, 0.4);
+        if census.add_soup(&mut universe, 2000).unwrap() {
+            settled += 1;
+        }
+    }
+    assert!(settled > 0);
+    assert!(census.total() > 0);
+    assert_eq!(census.table().lines().count(), census.entries().len());
+}
@@ -0,0 +1,79 @@
+use wasm_game_of_life::{InitType, Universe, UniverseError, MAX_STEP_POW2};
+
+fn soup(width: u32, height: u32, seed: u64) -> Universe {
+    let mut universe = Universe::with_size(width, height, InitType::Clear).unwrap();
+    universe.set_seed(seed);
+    universe.put_random_region(0, 0, width, height, 0.35);
+    universe
+}
+
+fn assert_matches_ticks(width: u32, height: u32, seed: u64, rule: &str) {
+    for k in 0..6 {
+        let mut expected = soup(width, height, seed);
+        let mut universe = soup(width, height, seed);
+        expected.set_rule_string(rule).unwrap();
+        universe.set_rule_string(rule).unwrap();
+        for _ in 0..1 << k {
+            expected.tick();
+        }
+        universe.step_pow2(k).unwrap();
+        assert_eq!(
+            universe.to_plaintext(),
+            expected.to_plaintext(),
+            "{}x{} seed {} rule {} k {}",
+            width,
+            height,
+            seed,
+            rule,
+            k
+        );
+    }
+}
+
+#[test]
+fn matches_tick_on_square_tori() {
+    assert_matches_ticks(16, 16, 1, "B3/S23");
+    assert_matches_ticks(32, 32, 2, "B3/S23");
+}
+
+#[test]
+fn matches_tick_on_odd_tori() {
+    assert_matches_ticks(13, 7, 3, "B3/S23");
+    assert_matches_ticks(5, 20, 4, "B3/S23");
+    assert_matches_ticks(2, 9, 5, "B3/S23");
+    assert_matches_ticks(1, 6, 10, "B3/S23");
+}
+
+#[test]
+fn matches_tick_for_other_rules() {
+    assert_matches_ticks(12, 10, 6, "B36/S23");
+    assert_matches_ticks(12, 10, 7, "B3678/S34678");
+    assert_matches_ticks(12, 10, 8, "B0/S8");
+}
+
+#[test]
+fn repeated_jumps_accumulate() {
+    let mut expected = soup(20, 20, 9);
+    let mut universe = soup(20, 20, 9);
+    for _ in 0..24 {
+        expected.tick();
+    }
+    universe.step_pow2(4).unwrap();
+    universe.step_pow2(3).unwrap();
+    assert_eq!(universe.to_plaintext(), expected.to_plaintext());
+}
+
+#[test]
+fn huge_jumps_on_a_torus() {
+    // a glider on a 16x16 torus comes back every 64 generations
+    let mut universe = Universe::with_size(16, 16, InitType::Clear).unwrap();
+    universe.put_glider();
+    let start = universe.to_plaintext();
+    universe.step_pow2(40).unwrap();
+    assert_eq!(universe.to_plaintext(), start);
+
+    assert_eq!(
+        universe.step_pow2(MAX_STEP_POW2 + 1),
+        Err(UniverseError::StepTooLarge(MAX_STEP_POW2 + 1))
+    );
+}
@@ -0,0 +1,134 @@
+use wasm_game_of_life::{InitType, Pattern, Transform, Universe};
+
+fn soup(seed: u64) -> Universe {
+    let mut universe = Universe::with_size(48, 40, InitType::Clear).unwrap();
+    universe.set_seed(seed);
+    universe.put_random_region(0, 0, 48, 40, 0.3);
+    universe
+}
+
+#[test]
+fn history_is_off_by_default() {
+    let mut universe = soup(1);
+    universe.tick();
+    assert_eq!(universe.generation(), 1);
+    assert!(!universe.undo());
+    assert_eq!(universe.generation(), 1);
+}
+
+#[test]
+fn undo_and_redo_ticks() {
+    let mut universe = soup(2);
+    universe.set_history_capacity(100);
+    let mut states = vec![universe.to_rle()];
+    for _ in 0..10 {
+        universe.tick();
+        states.push(universe.to_rle());
+    }
+    assert_eq!(universe.generation(), 10);
+
+    for generation in (0..10).rev() {
+        assert!(universe.undo());
+        assert_eq!(universe.generation(), generation);
+        assert_eq!(universe.to_rle(), states[generation as usize]);
+    }
+    for state in &states[1..] {
+        assert!(universe.redo());
+        assert_eq!(&universe.to_rle(), state);
+    }
+    assert!(!universe.redo());
+
+    // Ticking again after an undo continues from the restored state.
+    assert_eq!(universe.rewind(4), 4);
+    universe.tick();
+    assert_eq!(universe.to_rle(), states[7]);
+    assert!(!universe.redo());
+}
+
+#[test]
+fn edits_are_undoable() {
+    let mut universe = soup(3);
+    universe.set_history_capacity(10);
+    let start = universe.to_rle();
+
+    universe.put_pattern(&Pattern::glider(), 20, 20, Transform::Identity);
+    let with_glider = universe.to_rle();
+    universe.clear();
+    assert_eq!(universe.population(), 0);
+
+    assert!(universe.undo());
+    assert_eq!(universe.to_rle(), with_glider);
+    assert!(universe.undo());
+    assert_eq!(universe.to_rle(), start);
+    assert_eq!(universe.generation(), 0);
+    assert!(!universe.undo());
+}
+
+#[test]
+fn rewind_goes_through_edits_and_jumps() {
+    let mut universe = soup(4);
+    universe.set_history_capacity(50);
+    let start = universe.to_rle();
+    universe.tick();
+    universe.put_pattern(&Pattern::glider(), 5, 5, Transform::Identity);
+    universe.step_pow2(3).unwrap();
+    universe.tick();
+    assert_eq!(universe.generation(), 10);
+
+    assert_eq!(universe.rewind(3), 9);
+    assert_eq!(universe.generation(), 1);
+    assert_eq!(universe.rewind(100), 1);
+    assert_eq!(universe.to_rle(), start);
+}
+
+#[test]
+fn capacity_bounds_the_history() {
+    let mut universe = soup(5);
+    universe.set_history_capacity(3);
+    for _ in 0..10 {
+        universe.tick();
+    }
+    assert_eq!(universe.rewind(10), 3);
+    assert_eq!(universe.generation(), 7);
+
+    // Resizing starts a new history.
+    universe.redo();
+    universe.resize(50, 40, Default::default()).unwrap();
+    assert!(!universe.undo());
+}
+
+#[test]
+fn shrinking_the_capacity_keeps_the_next_redo() {
+    let mut universe = soup(6);
+    universe.set_history_capacity(3);
+    universe.tick();
+    let first = universe.to_rle();
+    universe.tick();
+    let second = universe.to_rle();
+    universe.tick();
+    assert_eq!(universe.rewind(3), 3);
+
+    universe.set_history_capacity(1);
+    assert!(universe.redo());
+    assert_eq!(universe.generation(), 1);
+    assert_eq!(universe.to_rle(), first);
+    assert!(!universe.redo());
+
+    universe.tick();
+    assert_eq!(universe.to_rle(), second);
+    assert!(universe.undo());
+    assert!(!universe.undo());
+    assert_eq!(universe.to_rle(), first);
+}
+
+#[test]
+fn undo_reports_flipped_cells() {
+    let mut universe = Universe::with_size(8, 8, InitType::Clear).unwrap();
+    universe.set_history_capacity(4);
+    universe.load_plaintext("OOO").unwrap();
+    universe.tick();
+    universe.undo();
+    let mut flipped = universe.changed_cell_indices().to_vec();
+    flipped.sort_unstable();
+    assert_eq!(flipped, vec![2 * 8 + 3, 3 * 8 + 2, 3 * 8 + 4, 4 * 8 + 3]);
+}
@@ -0,0 +1,166 @@
+use wasm_game_of_life::{
+    BoundingBox, InfiniteUniverse, InitType, Pattern, Pcg32, RenderOptions, Rle, Transform,
+    Universe, UniverseError,
+};
+
+fn sorted(mut cells: Vec<(i32, i32)>) -> Vec<(i32, i32)> {
+    cells.sort_unstable();
+    cells
+}
+
+#[test]
+fn glider_travels_without_wrapping() {
+    let mut universe = InfiniteUniverse::new();
+    universe.put_pattern(&Pattern::glider(), 0, 0, Transform::Identity);
+    let start = sorted(universe.live_cells());
+
+    // Far enough to cross several chunk boundaries.
+    for _ in 0..4 * 100 {
+        universe.tick();
+    }
+    let moved: Vec<_> = start.iter().map(|&(c, r)| (c + 100, r + 100)).collect();
+    assert_eq!(sorted(universe.live_cells()), moved);
+    assert_eq!(universe.population(), 5);
+}
+
+#[test]
+fn glider_crosses_into_negative_coordinates() {
+    let mut universe = InfiniteUniverse::new();
+    universe.put_pattern(&Pattern::glider(), 0, 0, Transform::Rotate180);
+    let start = sorted(universe.live_cells());
+
+    for _ in 0..4 * 20 {
+        universe.tick();
+    }
+    let moved: Vec<_> = start.iter().map(|&(c, r)| (c - 20, r - 20)).collect();
+    assert_eq!(sorted(universe.live_cells()), moved);
+}
+
+#[test]
+fn bounding_box_follows_the_pattern() {
+    let mut universe = InfiniteUniverse::new();
+    assert_eq!(universe.bounding_box(), None);
+
+    universe.set_cell(-70, 5, true);
+    universe.set_cell(3, -2, true);
+    assert_eq!(
+        universe.bounding_box(),
+        Some(BoundingBox {
+            col: -70,
+            row: -2,
+            width: 74,
+            height: 8,
+        })
+    );
+
+    universe.set_cell(-70, 5, false);
+    assert_eq!(
+        universe.bounding_box(),
+        Some(BoundingBox {
+            col: 3,
+            row: -2,
+            width: 1,
+            height: 1,
+        })
+    );
+
+    // Lone cells die and their chunks are dropped.
+    universe.tick();
+    assert_eq!(universe.bounding_box(), None);
+    assert_eq!(universe.population(), 0);
+}
+
+#[test]
+fn renders_the_bounding_box() {
+    let mut universe = InfiniteUniverse::new();
+    universe.load_rle("x = 3, y = 1\n3o!").unwrap();
+    let options = RenderOptions::new();
+    assert_eq!(universe.render(&options), "◻◻◻\n");
+
+    universe.tick();
+    assert_eq!(universe.render(&options), "◻\n◻\n◻\n");
+    assert_eq!(universe.to_string(), universe.render(&options));
+}
+
+#[test]
+fn rejects_b0_rules() {
+    let mut universe = InfiniteUniverse::new();
+    assert!(matches!(
+        universe.set_rule_string("B0/S8"),
+        Err(UniverseError::UnsupportedRule(_))
+    ));
+    assert_eq!(universe.rule_string(), "B3/S23");
+    universe.set_rule_string("B36/S23").unwrap();
+    assert_eq!(universe.rule_string(), "B36/S23");
+}
+
+#[test]
+fn matches_a_large_torus_before_anything_wraps() {
+    const SIZE: u32 = 160;
+    const SOUP: u32 = 24;
+    const OFFSET: i32 = 80;
+
+    let mut rng = Pcg32::new(12);
+    let soup: Vec<_> = (0..SOUP)
+        .flat_map(|row| (0..SOUP).map(move |col| (col, row)))
+        .filter(|_| rng.chance(0.4))
+        .collect();
+
+    // Straddle the chunk corner at the origin.
+    let pattern = Pattern::new(soup);
+    let mut infinite = InfiniteUniverse::new();
+    infinite.put_pattern(&pattern, -12, -12, Transform::Identity);
+    let mut torus = Universe::with_size(SIZE, SIZE, InitType::Clear).unwrap();
+    pattern.place(&mut torus, OFFSET - 12, OFFSET - 12, Transform::Identity);
+
+    // Nothing can travel faster than light, so 60 generations stay well
+    // clear of the torus seam.
+    for _ in 0..60 {
+        infinite.tick();
+        torus.tick();
+    }
+    let rle: Rle = torus.to_rle().parse().unwrap();
+    let expected: Vec<_> = rle
+        .cells
+        .iter()
+        .map(|&(col, row)| (col as i32 - OFFSET, row as i32 - OFFSET))
+        .collect();
+    assert_eq!(sorted(infinite.live_cells()), sorted(expected));
+}
+
+#[test]
+fn cells_past_the_edge_of_the_plane_are_dropped() {
+    let mut universe = InfiniteUniverse::new();
+    universe.put_pattern(&Pattern::glider(), i32::MAX - 1, 0, Transform::Identity);
+    assert_eq!(
+        sorted(universe.live_cells()),
+        vec![(i32::MAX - 1, 2), (i32::MAX, 0), (i32::MAX, 2)]
+    );
+
+    // A blinker on the right edge would grow a cell past it.
+    let mut universe = InfiniteUniverse::new();
+    for row in 0..3 {
+        universe.set_cell(i32::MAX, row, true);
+    }
+    universe.tick();
+    assert_eq!(
+        sorted(universe.live_cells()),
+        vec![(i32::MAX - 1, 1), (i32::MAX, 1)]
+    );
+    assert_eq!(
+        universe.bounding_box(),
+        Some(BoundingBox {
+            col: i32::MAX - 1,
+            row: 1,
+            width: 2,
+            height: 1,
+        })
+    );
+
+    let mut universe = InfiniteUniverse::new();
+    universe.set_cell(i32::MIN, i32::MIN, true);
+    universe.set_cell(i32::MAX, i32::MAX, true);
+    let bounds = universe.bounding_box().unwrap();
+    assert_eq!((bounds.col, bounds.row), (i32::MIN, i32::MIN));
+    assert_eq!((bounds.width, bounds.height), (u32::MAX, u32::MAX));
+}
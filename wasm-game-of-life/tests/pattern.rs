@@ -0,0 +1,47 @@
+use wasm_game_of_life::{InitType, Pattern, Transform, Universe};
+
+fn placed(pattern: &Pattern, col: i32, row: i32, transform: Transform) -> String {
+    let mut universe = Universe::with_size(5, 4, InitType::Clear).unwrap();
+    pattern.place(&mut universe, col, row, transform);
+    universe.to_plaintext()
+}
+
+#[test]
+fn applies_dihedral_transforms() {
+    let glider = Pattern::glider();
+    let cases = [
+        (Transform::Identity, ".O...\n..O..\nOOO..\n.....\n"),
+        (Transform::Rotate90, "O....\nO.O..\nOO...\n.....\n"),
+        (Transform::Rotate180, "OOO..\nO....\n.O...\n.....\n"),
+        (Transform::Rotate270, ".OO..\nO.O..\n..O..\n.....\n"),
+        (Transform::FlipHorizontal, ".O...\nO....\nOOO..\n.....\n"),
+        (Transform::FlipVertical, "OOO..\n..O..\n.O...\n.....\n"),
+        (Transform::FlipDiagonal, "..O..\nO.O..\n.OO..\n.....\n"),
+        (Transform::FlipAntiDiagonal, "OO...\nO.O..\nO....\n.....\n"),
+    ];
+    for (transform, expected) in cases.iter() {
+        assert_eq!(placed(&glider, 0, 0, *transform), *expected, "{:?}", transform);
+    }
+}
+
+#[test]
+fn transforms_swap_bounding_box() {
+    let spaceship = Pattern::spaceship();
+    assert_eq!((spaceship.width(), spaceship.height()), (5, 4));
+    let rotated = spaceship.transformed(Transform::Rotate90);
+    assert_eq!((rotated.width(), rotated.height()), (4, 5));
+    assert_eq!(rotated.population(), spaceship.population());
+}
+
+#[test]
+fn placement_wraps_around_edges() {
+    let glider = Pattern::glider();
+    assert_eq!(
+        placed(&glider, 3, 2, Transform::Identity),
+        "O..OO\n.....\n....O\nO....\n"
+    );
+    assert_eq!(
+        placed(&glider, -2, -2, Transform::Identity),
+        "O..OO\n.....\n....O\nO....\n"
+    );
+}
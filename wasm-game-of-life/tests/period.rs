@@ -0,0 +1,79 @@
+use wasm_game_of_life::{InitType, Pattern, PeriodDetector, Periodicity, Transform, Universe};
+
+fn universe_with(pattern: &str, size: u32) -> Universe {
+    let mut universe = Universe::with_size(size, size, InitType::Clear).unwrap();
+    Pattern::from_plaintext(pattern)
+        .unwrap()
+        .place(&mut universe, 4, 4, Transform::Identity);
+    universe
+}
+
+fn periodicity(period: u64, start: u64, dx: i32, dy: i32) -> Option<Periodicity> {
+    Some(Periodicity {
+        period,
+        start,
+        dx,
+        dy,
+    })
+}
+
+#[test]
+fn finds_still_lifes_and_oscillators() {
+    let mut block = universe_with("OO\nOO", 16);
+    assert_eq!(block.run_until_periodic(10), periodicity(1, 0, 0, 0));
+
+    let mut pulsar = universe_with(
+        "..OOO...OOO..\n\n\
+         O....O.O....O\nO....O.O....O\nO....O.O....O\n..OOO...OOO..\n\n\
+         ..OOO...OOO..\nO....O.O....O\nO....O.O....O\nO....O.O....O\n\n..OOO...OOO..",
+        24,
+    );
+    assert_eq!(pulsar.run_until_periodic(10), periodicity(3, 0, 0, 0));
+    assert_eq!(pulsar.generation(), 3);
+}
+
+#[test]
+fn finds_where_the_cycle_starts() {
+    // A pre-block turns into a block after one generation and a lone cell
+    // dies, leaving nothing behind.
+    let mut pre_block = universe_with("OO\nO.", 16);
+    assert_eq!(pre_block.run_until_periodic(10), periodicity(1, 1, 0, 0));
+
+    let mut lone = universe_with("O", 16);
+    assert_eq!(lone.run_until_periodic(10), periodicity(1, 1, 0, 0));
+    assert_eq!(lone.population(), 0);
+}
+
+#[test]
+fn measures_spaceship_displacement() {
+    let mut glider = universe_with(".O.\n..O\nOOO", 32);
+    assert_eq!(glider.run_until_periodic(10), periodicity(4, 0, 1, 1));
+
+    let mut lwss = universe_with(".O..O\nO....\nO...O\nOOOO.", 32);
+    assert_eq!(lwss.run_until_periodic(10), periodicity(4, 0, -2, 0));
+}
+
+#[test]
+fn gives_up_after_the_budget() {
+    let mut glider = universe_with(".O.\n..O\nOOO", 32);
+    assert_eq!(glider.run_until_periodic(3), None);
+    assert_eq!(glider.generation(), 3);
+}
+
+#[test]
+fn restarts_after_going_back() {
+    let mut universe = universe_with("OOO", 16);
+    universe.set_history_capacity(4);
+    let mut detector = PeriodDetector::new();
+    assert_eq!(detector.observe(&universe), None);
+    universe.tick();
+    assert_eq!(detector.observe(&universe), None);
+
+    // Going back to generation 0 is not a cycle of period 0.
+    universe.undo();
+    assert_eq!(detector.observe(&universe), None);
+    universe.tick();
+    assert_eq!(detector.observe(&universe), None);
+    universe.tick();
+    assert_eq!(detector.observe(&universe), periodicity(2, 0, 0, 0));
+}
@@ -0,0 +1,43 @@
+use wasm_game_of_life::{InitType, Plaintext, Universe};
+
+#[test]
+fn parses_comments_and_rows() {
+    let pattern: Plaintext = "!Name: Glider\n!The smallest spaceship.\n.O\n..O\nOOO\n"
+        .parse()
+        .unwrap();
+    assert_eq!(pattern.name.as_deref(), Some("Glider"));
+    assert_eq!(pattern.comments, vec!["The smallest spaceship."]);
+    assert_eq!((pattern.width, pattern.height), (3, 3));
+    assert_eq!(pattern.cells, vec![(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+    assert_eq!(
+        pattern.to_string(),
+        "!Name: Glider\n!The smallest spaceship.\n.O.\n..O\nOOO\n"
+    );
+}
+
+#[test]
+fn reports_unexpected_characters() {
+    let err = "!Name: Broken\n.O.\n.x.\n".parse::<Plaintext>().unwrap_err();
+    assert_eq!((err.line, err.column), (3, 2));
+}
+
+#[test]
+fn writes_cells_outside_the_size() {
+    let mut pattern: Plaintext = "OO\nOO".parse().unwrap();
+    pattern.width = 1;
+    assert_eq!(pattern.to_string(), "OO\nOO\n");
+    pattern.height = 3;
+    assert_eq!(pattern.to_string(), "OO\nOO\n..\n");
+}
+
+#[test]
+fn universe_round_trip() {
+    let mut universe = Universe::with_size(6, 4, InitType::Clear).unwrap();
+    universe.load_plaintext(".O\n..O\nOOO").unwrap();
+    let output = universe.to_plaintext();
+    assert_eq!(output, "..O...\n...O..\n.OOO..\n......\n");
+
+    let mut copy = Universe::with_size(6, 4, InitType::Clear).unwrap();
+    copy.load_plaintext(&output).unwrap();
+    assert_eq!(copy.to_plaintext(), output);
+}
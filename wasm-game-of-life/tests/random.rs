@@ -0,0 +1,50 @@
+use wasm_game_of_life::{InitType, Pcg32, Universe};
+
+#[test]
+fn pcg32_is_deterministic() {
+    let mut a = Pcg32::new(42);
+    let mut b = Pcg32::new(42);
+    let mut c = Pcg32::new(43);
+    let first: Vec<u32> = (0..8).map(|_| a.next_u32()).collect();
+    assert_eq!(first, (0..8).map(|_| b.next_u32()).collect::<Vec<_>>());
+    assert_ne!(first, (0..8).map(|_| c.next_u32()).collect::<Vec<_>>());
+    assert!((0..1000).all(|_| (0.0..1.0).contains(&a.next_f64())));
+}
+
+#[test]
+fn seeded_universes_replay_the_same_soup() {
+    let mut a = Universe::new_seeded(1234, 0.3);
+    let mut b = Universe::new_seeded(1234, 0.3);
+    assert_eq!(a.seed(), 1234);
+    assert_eq!(a.to_rle(), b.to_rle());
+    for _ in 0..10 {
+        a.tick();
+        b.tick();
+    }
+    assert_eq!(a.to_rle(), b.to_rle());
+    assert_ne!(a.to_rle(), Universe::new_seeded(1235, 0.3).to_rle());
+}
+
+#[test]
+fn random_region_wraps_around_edges() {
+    let mut universe = Universe::with_size(8, 8, InitType::Clear).unwrap();
+    universe.set_seed(7);
+    universe.put_random_region(2, 2, 3, 3, 1.0);
+    assert_eq!(
+        universe.to_plaintext(),
+        "........\n........\n..OOO...\n..OOO...\n..OOO...\n........\n........\n........\n"
+    );
+
+    universe.put_random_region(0, 0, 8, 8, 0.0);
+    universe.put_random_region(6, 6, 4, 4, 1.0);
+    assert_eq!(
+        universe.to_plaintext(),
+        "OO....OO\nOO....OO\n........\n........\n........\n........\nOO....OO\nOO....OO\n"
+    );
+}
+
+#[test]
+fn random_init_works_natively() {
+    let universe = Universe::with_size(16, 16, InitType::Random).unwrap();
+    assert!(universe.to_plaintext().contains('O'));
+}
@@ -0,0 +1,38 @@
+use wasm_game_of_life::{InitType, RenderOptions, Universe};
+
+fn glider() -> Universe {
+    let mut universe = Universe::with_size(5, 4, InitType::Clear).unwrap();
+    universe.load_plaintext(".O\n..O\nOOO").unwrap();
+    universe
+}
+
+#[test]
+fn renders_every_cell() {
+    let universe = glider();
+    assert_eq!(
+        universe.to_string(),
+        "◼◼◻◼◼\n◼◼◼◻◼\n◼◻◻◻◼\n◼◼◼◼◼\n"
+    );
+    assert_eq!(universe.render(&RenderOptions::default()), universe.to_string());
+}
+
+#[test]
+fn renders_with_custom_glyphs_and_coordinates() {
+    let mut options = RenderOptions::new();
+    options.set_alive('#');
+    options.set_dead('.');
+    options.set_coordinates(true);
+    assert_eq!(
+        glider().render(&options),
+        "  01234\n0 ..#..\n1 ...#.\n2 .###.\n3 .....\n"
+    );
+}
+
+#[test]
+fn renders_clipped_viewport() {
+    let mut options = RenderOptions::new();
+    options.set_alive('O');
+    options.set_dead('.');
+    options.set_viewport(2, 1, 10, 2);
+    assert_eq!(glider().render(&options), ".O.\nOO.\n");
+}
@@ -0,0 +1,75 @@
+use wasm_game_of_life::{InitType, ParseError, Rle, Rule, Universe};
+
+const GOSPER_GLIDER_GUN: &str = "\
+#N Gosper glider gun
+#C This was the first gun discovered.
+#C As its name suggests, it was discovered by Bill Gosper.
+x = 36, y = 9, rule = B3/S23
+24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4b
+obo$10bo5bo7bo$11bo3bo$12b2o!
+";
+
+#[test]
+fn parses_header_comments_and_cells() {
+    let rle: Rle = GOSPER_GLIDER_GUN.parse().unwrap();
+    assert_eq!(rle.name.as_deref(), Some("Gosper glider gun"));
+    assert_eq!(rle.comments.len(), 2);
+    assert_eq!((rle.width, rle.height), (36, 9));
+    assert_eq!(rle.rule, Some(Rule::CONWAY));
+    assert_eq!(rle.cells.len(), 36);
+    assert_eq!(rle.cells[0], (24, 0));
+}
+
+#[test]
+fn writes_wrapped_lines() {
+    let rle: Rle = GOSPER_GLIDER_GUN.parse().unwrap();
+    let output = rle.to_string();
+    assert!(output.lines().all(|line| line.len() <= 70));
+    assert_eq!(output.parse::<Rle>().unwrap(), rle);
+}
+
+#[test]
+fn universe_round_trip() {
+    let mut universe = Universe::with_size(8, 8, InitType::Clear).unwrap();
+    universe
+        .load_rle("x = 3, y = 3, rule = B36/S23\nbo$2bo$3o!")
+        .unwrap();
+    assert_eq!(universe.rule_string(), "B36/S23");
+    assert_eq!(
+        universe.to_rle(),
+        "x = 8, y = 8, rule = B36/S23\n2$3bo$4bo$2b3o!\n"
+    );
+
+    let mut copy = Universe::with_size(8, 8, InitType::Clear).unwrap();
+    copy.load_rle(&universe.to_rle()).unwrap();
+    assert_eq!(copy.to_rle(), universe.to_rle());
+}
+
+#[test]
+fn universe_grows_to_fit_pattern() {
+    let mut universe = Universe::with_size(8, 8, InitType::Clear).unwrap();
+    universe.load_rle(GOSPER_GLIDER_GUN).unwrap();
+    assert_eq!((universe.width(), universe.height()), (36, 9));
+}
+
+#[test]
+fn reports_error_positions() {
+    let err = "#N test\nx = 3, y = three".parse::<Rle>().unwrap_err();
+    assert_eq!((err.line, err.column), (2, 12));
+
+    let err = "x = 3, y = 3, rule = B9/S23".parse::<Rle>().unwrap_err();
+    assert_eq!((err.line, err.column), (1, 22));
+
+    let err = "x = 3, y = 3\nbo$2bo$\n3o2o!".parse::<Rle>().unwrap_err();
+    assert_eq!(
+        err,
+        ParseError {
+            line: 3,
+            column: 4,
+            message: "cells run past the 3x3 bounding box".to_string(),
+        }
+    );
+
+    let err = "x = 3, y = 3\nbo$2bq!".parse::<Rle>().unwrap_err();
+    assert_eq!((err.line, err.column), (2, 6));
+}
@@ -0,0 +1,53 @@
+use wasm_game_of_life::{InitType, Rule, RuleParseError, Universe};
+
+#[test]
+fn parses_common_rules() {
+    for (input, expected) in &[
+        ("B3/S23", "B3/S23"),
+        ("B36/S23", "B36/S23"),
+        ("B2/S", "B2/S"),
+        ("B3678/S34678", "B3678/S34678"),
+        ("b3/s23", "B3/S23"),
+        ("S23/B3", "B3/S23"),
+        ("23/36", "B36/S23"),
+    ] {
+        let rule: Rule = input.parse().unwrap();
+        assert_eq!(rule.to_string(), *expected);
+    }
+    assert_eq!("B3/S23".parse::<Rule>().unwrap(), Rule::CONWAY);
+}
+
+#[test]
+fn rejects_malformed_rules() {
+    assert_eq!("B3S23".parse::<Rule>(), Err(RuleParseError::MissingSeparator));
+    assert_eq!("B39/S23".parse::<Rule>(), Err(RuleParseError::InvalidCount(9)));
+    assert_eq!("B3x/S23".parse::<Rule>(), Err(RuleParseError::InvalidDigit('x')));
+    assert_eq!("B33/S23".parse::<Rule>(), Err(RuleParseError::DuplicateCount(3)));
+    assert!(matches!(
+        "X3/S23".parse::<Rule>(),
+        Err(RuleParseError::InvalidPrefix(_))
+    ));
+}
+
+fn is_alive(universe: &Universe, row: u32, col: u32) -> bool {
+    let size = (universe.width() * universe.height()) as usize;
+    let words = unsafe { std::slice::from_raw_parts(universe.cells(), size.div_ceil(32)) };
+    let idx = (row * universe.width() + col) as usize;
+    words[idx / 32] & (1 << (idx % 32)) != 0
+}
+
+#[test]
+fn seeds_kills_every_live_cell() {
+    let mut universe = Universe::with_size(16, 16, InitType::Clear).unwrap();
+    universe.set_rule("B2/S".parse().unwrap());
+    // six cells in a row at row 5, columns 5..11
+    universe.put_nebra();
+    universe.tick();
+
+    assert!((5..11).all(|col| !is_alive(&universe, 5, col)));
+    // only the cells diagonal to both ends see exactly two neighbors
+    for &(row, col) in &[(4, 5), (4, 10), (6, 5), (6, 10)] {
+        assert!(is_alive(&universe, row, col));
+    }
+    assert!(!is_alive(&universe, 4, 6));
+}
@@ -0,0 +1,38 @@
+// Run with `cargo test --features simd`.
+#![cfg(feature = "simd")]
+
+use proptest::prelude::*;
+use wasm_game_of_life::{InitType, Rule, Universe};
+
+fn rule() -> impl Strategy<Value = Rule> {
+    (0u16..1 << 9, 0u16..1 << 9).prop_map(|(birth, survival)| {
+        let counts = |mask: u16| (0..9).filter(|n| mask & (1 << n) != 0).collect::<Vec<u8>>();
+        Rule::new(&counts(birth), &counts(survival)).unwrap()
+    })
+}
+
+proptest! {
+    #[test]
+    fn simd_tick_matches_scalar(
+        width in 1u32..300,
+        height in 1u32..20,
+        seed in any::<u64>(),
+        rule in rule(),
+    ) {
+        let mut simd = Universe::with_size(width, height, InitType::Clear).unwrap();
+        simd.set_seed(seed);
+        simd.put_random();
+        simd.set_rule(rule);
+        let mut scalar = Universe::with_size(width, height, InitType::Clear).unwrap();
+        scalar.set_seed(seed);
+        scalar.put_random();
+        scalar.set_rule(rule);
+        scalar.set_simd(false);
+
+        for _ in 0..4 {
+            simd.tick();
+            scalar.tick();
+            prop_assert_eq!(simd.to_rle(), scalar.to_rle());
+        }
+    }
+}
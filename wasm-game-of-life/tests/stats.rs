@@ -0,0 +1,94 @@
+use wasm_game_of_life::{BoundingBox, Cell, InitType, Universe};
+
+#[test]
+fn records_each_tick() {
+    let mut universe = Universe::with_size(10, 8, InitType::Clear).unwrap();
+    universe.set_cells(&[(4, 3), (4, 4), (4, 5)], Cell::Alive).unwrap();
+    universe.tick();
+    assert!(universe.stats_population().is_empty());
+
+    universe.set_stats_capacity(4);
+    universe.tick();
+    universe.tick();
+    assert_eq!(universe.stats_generations(), vec![2, 3]);
+    assert_eq!(universe.stats_population(), vec![3, 3]);
+    assert_eq!(universe.stats_births(), vec![2, 2]);
+    assert_eq!(universe.stats_deaths(), vec![2, 2]);
+    assert_eq!(universe.stats_bounding_boxes(), vec![3, 4, 3, 1, 4, 3, 1, 3]);
+    assert_eq!(universe.stats_density(), vec![3.0 / 80.0; 2]);
+}
+
+#[test]
+fn keeps_the_latest_samples() {
+    let mut universe = Universe::new_seeded(3, 0.3);
+    universe.set_stats_capacity(5);
+    universe.step(20);
+    assert_eq!(universe.stats_generations(), vec![16, 17, 18, 19, 20]);
+    assert_eq!(*universe.stats_population().last().unwrap(), universe.population());
+
+    universe.set_stats_capacity(2);
+    assert_eq!(universe.stats_generations(), vec![19, 20]);
+    universe.set_stats_capacity(0);
+    universe.tick();
+    assert!(universe.stats_generations().is_empty());
+}
+
+#[test]
+fn undo_drops_later_samples() {
+    let mut universe = Universe::new_seeded(5, 0.3);
+    universe.set_history_capacity(10);
+    universe.set_stats_capacity(10);
+    universe.step(4);
+    universe.rewind(2);
+    assert_eq!(universe.stats_generations(), vec![1, 2]);
+    universe.tick();
+    assert_eq!(universe.stats_generations(), vec![1, 2, 3]);
+}
+
+#[test]
+fn bounding_box_spans_words() {
+    let mut universe = Universe::with_size(100, 3, InitType::Clear).unwrap();
+    assert_eq!(universe.bounding_box(), None);
+    universe.set_stats_capacity(1);
+    universe.tick();
+    assert_eq!(universe.stats_bounding_boxes(), vec![0; 4]);
+
+    universe.set_cells(&[(1, 90), (2, 70)], Cell::Alive).unwrap();
+    assert_eq!(
+        universe.bounding_box(),
+        Some(BoundingBox {
+            col: 70,
+            row: 1,
+            width: 21,
+            height: 2,
+        })
+    );
+}
+
+#[test]
+fn redo_and_jumps_record_samples() {
+    let mut universe = Universe::with_size(16, 16, InitType::Clear).unwrap();
+    universe.set_cells(&[(4, 3), (4, 4), (4, 5)], Cell::Alive).unwrap();
+    universe.set_history_capacity(10);
+    universe.set_stats_capacity(10);
+    universe.tick();
+    universe.tick();
+    universe.undo();
+    universe.redo();
+    assert_eq!(universe.stats_generations(), vec![1, 2]);
+    assert_eq!(universe.stats_births(), vec![2, 2]);
+
+    // Four generations of a blinker change nothing in the end.
+    universe.step_pow2(2).unwrap();
+    assert_eq!(universe.stats_generations(), vec![1, 2, 6]);
+    assert_eq!(universe.stats_births(), vec![2, 2, 0]);
+    assert_eq!(universe.stats_population(), vec![3, 3, 3]);
+}
+
+#[test]
+fn huge_capacities_grow_as_needed() {
+    let mut universe = Universe::new_seeded(4, 0.3);
+    universe.set_stats_capacity(u32::MAX);
+    universe.step(3);
+    assert_eq!(universe.stats_generations(), vec![1, 2, 3]);
+}
@@ -0,0 +1,40 @@
+use wasm_game_of_life::{InitType, StepStats, Universe};
+
+#[test]
+fn counts_births_and_deaths() {
+    let mut universe = Universe::with_size(8, 8, InitType::Clear).unwrap();
+    universe.load_plaintext("OOO").unwrap();
+    let stats = universe.step(10);
+    assert_eq!(stats.population, 3);
+    assert_eq!(stats.births, 20);
+    assert_eq!(stats.deaths, 20);
+    assert!(stats.elapsed_ms >= 0.0);
+
+    let stats = universe.step(0);
+    assert_eq!(
+        stats,
+        StepStats {
+            population: 3,
+            elapsed_ms: stats.elapsed_ms,
+            ..StepStats::default()
+        }
+    );
+}
+
+#[test]
+fn matches_repeated_ticks() {
+    let mut stepped = Universe::new_seeded(7, 0.3);
+    let mut ticked = Universe::new_seeded(7, 0.3);
+    let before = stepped.population();
+
+    let stats = stepped.step(50);
+    for _ in 0..50 {
+        ticked.tick();
+    }
+    assert_eq!(stepped.to_rle(), ticked.to_rle());
+    assert_eq!(stats.population, ticked.population());
+    assert_eq!(
+        before as i64 + stats.births as i64 - stats.deaths as i64,
+        stats.population as i64
+    );
+}
@@ -0,0 +1,195 @@
+use proptest::prelude::*;
+use wasm_game_of_life::{InitType, Pattern, Rule, Transform, Universe};
+
+const GLIDER: &str = ".O.\n..O\nOOO";
+const BLINKER: &str = "OOO";
+const TOAD: &str = ".OOO\nOOO.";
+const BEACON: &str = "OO..\nOO..\n..OO\n..OO";
+const PULSAR: &str = "\
+..OOO...OOO..
+.............
+O....O.O....O
+O....O.O....O
+O....O.O....O
+..OOO...OOO..
+.............
+..OOO...OOO..
+O....O.O....O
+O....O.O....O
+O....O.O....O
+.............
+..OOO...OOO..";
+
+const BLOCK: &str = "OO\nOO";
+const BEEHIVE: &str = ".OO.\nO..O\n.OO.";
+const LOAF: &str = ".OO.\nO..O\n.O.O\n..O.";
+const BOAT: &str = "OO.\nO.O\n.O.";
+const TUB: &str = ".O.\nO.O\n.O.";
+
+fn universe_with(pattern: &str, width: u32, height: u32, col: i32, row: i32) -> Universe {
+    let mut universe = Universe::with_size(width, height, InitType::Clear).unwrap();
+    Pattern::from_plaintext(pattern)
+        .unwrap()
+        .place(&mut universe, col, row, Transform::Identity);
+    universe
+}
+
+fn run(universe: &mut Universe, generations: u32) {
+    for _ in 0..generations {
+        universe.tick();
+    }
+}
+
+fn assert_period(pattern: &str, period: u32) {
+    let mut universe = universe_with(pattern, 20, 20, 3, 3);
+    let start = universe.to_plaintext();
+    for generation in 1..period {
+        universe.tick();
+        assert_ne!(universe.to_plaintext(), start, "returned early at {}", generation);
+    }
+    universe.tick();
+    assert_eq!(universe.to_plaintext(), start);
+}
+
+#[test]
+fn oscillators_return_after_their_period() {
+    assert_period(BLINKER, 2);
+    assert_period(TOAD, 2);
+    assert_period(BEACON, 2);
+    assert_period(PULSAR, 3);
+}
+
+#[test]
+fn still_lifes_stay_fixed() {
+    for pattern in &[BLOCK, BEEHIVE, LOAF, BOAT, TUB] {
+        assert_period(pattern, 1);
+    }
+}
+
+#[test]
+fn glider_moves_diagonally_every_four_generations() {
+    let mut universe = universe_with(GLIDER, 12, 12, 2, 2);
+    for step in 1..=12 {
+        run(&mut universe, 4);
+        let expected = universe_with(GLIDER, 12, 12, 2 + step, 2 + step);
+        assert_eq!(universe.to_plaintext(), expected.to_plaintext(), "step {}", step);
+    }
+    // 12 steps of (1, 1) on a 12x12 torus bring the glider back home
+    assert_eq!(
+        universe.to_plaintext(),
+        universe_with(GLIDER, 12, 12, 2, 2).to_plaintext()
+    );
+}
+
+#[test]
+fn lone_cells_and_pairs_die() {
+    let mut universe = universe_with("O\n\n..OO", 8, 8, 1, 1);
+    universe.tick();
+    assert!(!universe.to_plaintext().contains('O'));
+}
+
+// A direct transcription of the rules, used as a reference for the engine.
+fn reference_tick(cells: &[Vec<bool>], rule: Rule) -> Vec<Vec<bool>> {
+    let height = cells.len();
+    let width = cells[0].len();
+    let mut next = vec![vec![false; width]; height];
+    for row in 0..height {
+        for col in 0..width {
+            let mut neighbors = 0;
+            for dr in [height - 1, 0, 1] {
+                for dc in [width - 1, 0, 1] {
+                    if (dr, dc) != (0, 0) && cells[(row + dr) % height][(col + dc) % width] {
+                        neighbors += 1;
+                    }
+                }
+            }
+            let mask = if cells[row][col] {
+                rule.survival()
+            } else {
+                rule.birth()
+            };
+            next[row][col] = mask & (1 << neighbors) != 0;
+        }
+    }
+    next
+}
+
+fn to_plaintext(cells: &[Vec<bool>]) -> String {
+    cells
+        .iter()
+        .map(|row| {
+            let mut line: String = row.iter().map(|&c| if c { 'O' } else { '.' }).collect();
+            line.push('\n');
+            line
+        })
+        .collect()
+}
+
+#[test]
+fn idle_edge_tiles_keep_their_columns() {
+    // A block across the seam sits in idle edge tiles, while the blinker
+    // keeps the middle tiles sharing their row words active.
+    let mut cells = vec![vec![false; 128]; 96];
+    for row in &mut cells[10..12] {
+        row[127] = true;
+        row[0] = true;
+    }
+    cells[45][60..63].fill(true);
+    let mut universe = Universe::with_size(128, 96, InitType::Clear).unwrap();
+    universe.load_plaintext(&to_plaintext(&cells)).unwrap();
+
+    for generation in 1..=4 {
+        universe.tick();
+        cells = reference_tick(&cells, Rule::CONWAY);
+        assert_eq!(universe.to_plaintext(), to_plaintext(&cells), "generation {}", generation);
+    }
+}
+
+fn soup() -> impl Strategy<Value = Vec<Vec<bool>>> {
+    // wide enough to span several 64-bit words per row
+    (3usize..150, 3usize..24).prop_flat_map(|(width, height)| {
+        prop::collection::vec(prop::collection::vec(any::<bool>(), width), height)
+    })
+}
+
+fn rule() -> impl Strategy<Value = Rule> {
+    (0u16..1 << 9, 0u16..1 << 9).prop_map(|(birth, survival)| {
+        let counts = |mask: u16| (0..9).filter(|n| mask & (1 << n) != 0).collect::<Vec<u8>>();
+        Rule::new(&counts(birth), &counts(survival)).unwrap()
+    })
+}
+
+proptest! {
+    #[test]
+    fn tick_matches_reference(cells in soup(), generations in 1u32..8) {
+        let mut universe = Universe::with_size(
+            cells[0].len() as u32,
+            cells.len() as u32,
+            InitType::Clear,
+        )
+        .unwrap();
+        universe.load_plaintext(&to_plaintext(&cells)).unwrap();
+
+        let mut expected = cells;
+        for _ in 0..generations {
+            universe.tick();
+            expected = reference_tick(&expected, Rule::CONWAY);
+        }
+        prop_assert_eq!(universe.to_plaintext(), to_plaintext(&expected));
+    }
+
+    #[test]
+    fn tick_matches_reference_for_any_rule(cells in soup(), rule in rule()) {
+        let mut universe = Universe::with_size(
+            cells[0].len() as u32,
+            cells.len() as u32,
+            InitType::Clear,
+        )
+        .unwrap();
+        universe.load_plaintext(&to_plaintext(&cells)).unwrap();
+        universe.set_rule(rule);
+
+        universe.tick();
+        prop_assert_eq!(universe.to_plaintext(), to_plaintext(&reference_tick(&cells, rule)));
+    }
+}
@@ -0,0 +1,158 @@
+use wasm_game_of_life::{Anchor, InitType, Pattern, Rle, Transform, Universe, TILE_SIZE};
+
+fn universe_with(pattern: &str, col: i32, row: i32) -> Universe {
+    let mut universe = Universe::with_size(4 * TILE_SIZE, 3 * TILE_SIZE, InitType::Clear).unwrap();
+    Pattern::from_plaintext(pattern)
+        .unwrap()
+        .place(&mut universe, col, row, Transform::Identity);
+    universe
+}
+
+#[test]
+fn still_lifes_settle() {
+    let mut universe = universe_with("OO\nOO", 40, 40);
+    assert_eq!(universe.tile_columns(), 4);
+    assert_eq!(universe.tile_rows(), 3);
+    assert_eq!(universe.changed_tiles().len(), 12);
+
+    universe.tick();
+    assert_eq!(universe.changed_tiles(), vec![]);
+    universe.tick();
+    assert_eq!(universe.changed_tiles(), vec![]);
+    assert_eq!(universe.to_plaintext(), universe_with("OO\nOO", 40, 40).to_plaintext());
+}
+
+#[test]
+fn oscillators_keep_their_tile_changing() {
+    let mut universe = universe_with("OOO", 40, 40);
+    for _ in 0..4 {
+        universe.tick();
+        assert_eq!(universe.changed_tiles(), vec![5]);
+    }
+}
+
+#[test]
+fn edits_mark_tiles() {
+    let mut universe = universe_with("OO\nOO", 40, 40);
+    universe.tick();
+    universe.put_pattern(&Pattern::glider(), 100, 70, Transform::Identity);
+    assert_eq!(universe.changed_tiles(), vec![11]);
+
+    // The glider wakes up its neighbors as it moves, and wraps across the
+    // corner of the torus into tile 0.
+    for _ in 0..4 * 30 {
+        universe.tick();
+    }
+    let mut expected = universe_with("OO\nOO", 40, 40);
+    expected.put_pattern(&Pattern::glider(), 130, 100, Transform::Identity);
+    assert_eq!(universe.to_plaintext(), expected.to_plaintext());
+    assert!(universe.changed_tiles().contains(&0));
+
+    universe.set_rule_string("B36/S23").unwrap();
+    assert_eq!(universe.changed_tiles().len(), 12);
+}
+
+#[test]
+fn matches_hashlife_across_edits() {
+    for seed in 0..4 {
+        let mut ticked = Universe::with_size(150, 100, InitType::Clear).unwrap();
+        ticked.set_seed(seed);
+        ticked.put_random_region(10, 10, 60, 40, 0.4);
+        ticked.put_pattern(&Pattern::glider(), 145, 95, Transform::Identity);
+        let mut jumped = Universe::with_size(150, 100, InitType::Clear).unwrap();
+        jumped.load_rle(&ticked.to_rle()).unwrap();
+
+        for round in 0..4 {
+            for _ in 0..16 {
+                ticked.tick();
+            }
+            jumped.step_pow2(4).unwrap();
+            assert_eq!(ticked.to_rle(), jumped.to_rle(), "seed {} round {}", seed, round);
+
+            let col = 30 * round;
+            ticked.put_pattern(&Pattern::spaceship(), col, 60, Transform::Rotate90);
+            jumped.put_pattern(&Pattern::spaceship(), col, 60, Transform::Rotate90);
+        }
+    }
+}
+
+fn live_indices(universe: &Universe) -> Vec<u32> {
+    let rle: Rle = universe.to_rle().parse().unwrap();
+    let mut cells: Vec<_> = rle
+        .cells
+        .iter()
+        .map(|&(col, row)| row * universe.width() + col)
+        .collect();
+    cells.sort_unstable();
+    cells
+}
+
+#[test]
+fn reports_flipped_cells() {
+    let mut universe = universe_with("OOO", 40, 40);
+    universe.tick();
+    let mut flipped = universe.changed_cell_indices().to_vec();
+    flipped.sort_unstable();
+    let width = universe.width();
+    assert_eq!(
+        flipped,
+        vec![39 * width + 41, 40 * width + 40, 40 * width + 42, 41 * width + 41]
+    );
+    assert_eq!(universe.changed_cells_len(), 4);
+
+    let mut soup = Universe::with_size(100, 70, InitType::Clear).unwrap();
+    soup.set_seed(3);
+    soup.put_random_region(0, 0, 40, 70, 0.4);
+    for _ in 0..20 {
+        let before = live_indices(&soup);
+        soup.tick();
+        let after = live_indices(&soup);
+        let mut expected: Vec<_> = before
+            .iter()
+            .filter(|idx| !after.contains(idx))
+            .chain(after.iter().filter(|idx| !before.contains(idx)))
+            .copied()
+            .collect();
+        expected.sort_unstable();
+        let mut flipped = soup.changed_cell_indices().to_vec();
+        flipped.sort_unstable();
+        assert_eq!(flipped, expected);
+    }
+}
+
+#[test]
+fn jumps_and_reloads_update_flipped_cells() {
+    let mut soup = Universe::with_size(64, 64, InitType::Clear).unwrap();
+    soup.set_seed(4);
+    soup.put_random_region(0, 0, 64, 64, 0.4);
+    let before = live_indices(&soup);
+    soup.step_pow2(3).unwrap();
+    let after = live_indices(&soup);
+    let mut expected: Vec<_> = before
+        .iter()
+        .filter(|idx| !after.contains(idx))
+        .chain(after.iter().filter(|idx| !before.contains(idx)))
+        .copied()
+        .collect();
+    expected.sort_unstable();
+    let mut flipped = soup.changed_cell_indices().to_vec();
+    flipped.sort_unstable();
+    assert_eq!(flipped, expected);
+
+    let mut universe = Universe::with_size(64, 64, InitType::Clear).unwrap();
+    universe.load_plaintext("OOO").unwrap();
+    universe.tick();
+    universe.resize(8, 8, Anchor::TopLeft).unwrap();
+    assert!(universe.changed_cell_indices().is_empty());
+
+    universe.load_plaintext("OOO").unwrap();
+    universe.tick();
+    universe.clear();
+    assert!(universe.changed_cell_indices().is_empty());
+
+    universe.load_plaintext("OOO").unwrap();
+    universe.tick();
+    universe.load_plaintext(&"O".repeat(20)).unwrap();
+    assert_eq!(universe.width(), 20);
+    assert!(universe.changed_cell_indices().is_empty());
+}
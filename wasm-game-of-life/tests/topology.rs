@@ -0,0 +1,129 @@
+use wasm_game_of_life::{InitType, Pattern, Rle, Topology, Transform, Universe, UniverseError};
+
+fn universe(spec: &str) -> Universe {
+    let mut universe = Universe::with_size(1, 1, InitType::Clear).unwrap();
+    universe.set_topology_string(spec).unwrap();
+    universe
+}
+
+fn live_cells(universe: &Universe) -> Vec<(u32, u32)> {
+    let rle: Rle = universe.to_rle().parse().unwrap();
+    let mut cells = rle.cells;
+    cells.sort_unstable();
+    cells
+}
+
+// Where a single cell placed at `(col, row)` ends up.
+fn placed_at(spec: &str, col: i32, row: i32) -> Vec<(u32, u32)> {
+    let mut universe = universe(spec);
+    Pattern::new(vec![(0, 0)]).place(&mut universe, col, row, Transform::Identity);
+    live_cells(&universe)
+}
+
+#[test]
+fn parses_golly_specs() {
+    let cases = [
+        ("P10,8", Topology::Bounded),
+        ("T10,8", Topology::Torus),
+        (
+            "T10+3,8",
+            Topology::ShiftedTorus {
+                col_shift: 3,
+                row_shift: 0,
+            },
+        ),
+        (
+            "T10,8-2",
+            Topology::ShiftedTorus {
+                col_shift: 0,
+                row_shift: -2,
+            },
+        ),
+        ("K10*,8", Topology::KleinBottle { flip_rows: false }),
+        ("K10,8*", Topology::KleinBottle { flip_rows: true }),
+        ("C10,8", Topology::CrossSurface),
+        ("S8", Topology::Sphere),
+    ];
+    for &(spec, topology) in &cases {
+        let universe = universe(spec);
+        assert_eq!(universe.topology(), topology, "{}", spec);
+        assert_eq!(universe.topology_string(), spec);
+    }
+
+    let mut universe = Universe::new(InitType::Clear);
+    for spec in ["", "T10", "X10,8", "T10+1,8+1", "K10*,8*", "S10,8", "T0,8", "P10,8,2"] {
+        assert!(
+            matches!(
+                universe.set_topology_string(spec),
+                Err(UniverseError::InvalidTopology(_)) | Err(UniverseError::InvalidSize { .. })
+            ),
+            "{:?}",
+            spec
+        );
+    }
+    assert_eq!(universe.topology_string(), "T64,64");
+}
+
+#[test]
+fn placement_follows_the_topology() {
+    assert_eq!(placed_at("T10,8", 1, 8), vec![(1, 0)]);
+    assert_eq!(placed_at("P10,8", 1, 8), vec![]);
+    assert_eq!(placed_at("T10+3,8", 1, 8), vec![(4, 0)]);
+    assert_eq!(placed_at("T10,8+3", -1, 2), vec![(9, 7)]);
+    assert_eq!(placed_at("K10*,8", 1, 8), vec![(8, 0)]);
+    assert_eq!(placed_at("K10,8*", 10, 2), vec![(0, 5)]);
+    assert_eq!(placed_at("C10,8", 1, -1), vec![(8, 7)]);
+    assert_eq!(placed_at("C10,8", -1, -1), vec![]);
+    assert_eq!(placed_at("S8", 3, -1), vec![(0, 3)]);
+    assert_eq!(placed_at("S8", 8, 2), vec![(2, 7)]);
+    assert_eq!(placed_at("S8", 8, 8), vec![]);
+}
+
+#[test]
+fn bounded_edges_stay_dead() {
+    let mut bounded = universe("P5,5");
+    let mut torus = universe("T5,5");
+    for universe in [&mut bounded, &mut torus] {
+        universe.load_plaintext(".OOO.\n.....\n.....\n.....\n.....").unwrap();
+        universe.tick();
+    }
+    assert_eq!(live_cells(&bounded), vec![(2, 0), (2, 1)]);
+    assert_eq!(live_cells(&torus), vec![(2, 0), (2, 1), (2, 4)]);
+}
+
+#[test]
+fn glider_comes_back_mirrored_on_a_klein_bottle() {
+    let mut universe = universe("K16*,16");
+    universe.put_glider();
+    let start = live_cells(&universe);
+
+    // After 64 generations the glider has moved 16 cells down and right, so
+    // it crossed the twisted edge exactly once.
+    for _ in 0..64 {
+        universe.tick();
+    }
+    let mut mirrored: Vec<_> = start.iter().map(|&(col, row)| (15 - col, row)).collect();
+    mirrored.sort_unstable();
+    assert_eq!(live_cells(&universe), mirrored);
+}
+
+#[test]
+fn sphere_stays_square() {
+    let mut universe = universe("S8");
+    assert!(matches!(
+        universe.resize(8, 4, Default::default()),
+        Err(UniverseError::InvalidTopology(_))
+    ));
+    universe.load_plaintext("OOOOOOOOOO").unwrap();
+    assert_eq!((universe.width(), universe.height()), (10, 10));
+    assert!(universe.set_topology(Topology::Bounded).is_ok());
+}
+
+#[test]
+fn step_pow2_needs_a_plain_torus() {
+    let mut universe = universe("P8,8");
+    assert_eq!(
+        universe.step_pow2(2),
+        Err(UniverseError::UnsupportedTopology(Topology::Bounded))
+    );
+}
@@ -0,0 +1,89 @@
+use wasm_game_of_life::{Anchor, InitType, Universe, UniverseError};
+
+fn live_cells(universe: &Universe) -> Vec<(u32, u32)> {
+    let size = (universe.width() * universe.height()) as usize;
+    let words = unsafe { std::slice::from_raw_parts(universe.cells(), size.div_ceil(32)) };
+    (0..size)
+        .filter(|&i| words[i / 32] & (1 << (i % 32)) != 0)
+        .map(|i| (i as u32 / universe.width(), i as u32 % universe.width()))
+        .collect()
+}
+
+#[test]
+fn with_size_sets_dimensions() {
+    let universe = Universe::with_size(100, 30, InitType::Clear).unwrap();
+    assert_eq!(universe.width(), 100);
+    assert_eq!(universe.height(), 30);
+}
+
+#[test]
+fn resize_pads_around_anchor() {
+    let mut universe = Universe::with_size(16, 16, InitType::Clear).unwrap();
+    universe.put_glider();
+    let before = live_cells(&universe);
+
+    universe.resize(32, 20, Anchor::BottomRight).unwrap();
+    assert_eq!((universe.width(), universe.height()), (32, 20));
+    let shifted: Vec<_> = before.iter().map(|&(r, c)| (r + 4, c + 16)).collect();
+    assert_eq!(live_cells(&universe), shifted);
+}
+
+#[test]
+fn resize_crops_around_center() {
+    let mut universe = Universe::with_size(16, 16, InitType::Clear).unwrap();
+    universe.put_glider();
+
+    universe.resize(6, 6, Anchor::Center).unwrap();
+    assert_eq!(
+        live_cells(&universe),
+        vec![(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]
+    );
+
+    universe.resize(2, 2, Anchor::TopLeft).unwrap();
+    assert_eq!(live_cells(&universe), vec![(0, 1)]);
+}
+
+#[test]
+fn rejects_invalid_sizes() {
+    assert_eq!(
+        Universe::with_size(0, 10, InitType::Clear).err(),
+        Some(UniverseError::InvalidSize {
+            width: 0,
+            height: 10
+        })
+    );
+    assert!(Universe::with_size(1 << 16, 1 << 16, InitType::Clear).is_err());
+
+    let mut universe = Universe::with_size(4, 4, InitType::Clear).unwrap();
+    assert!(universe.resize(4, 0, Anchor::Center).is_err());
+    assert_eq!((universe.width(), universe.height()), (4, 4));
+
+    // Patterns too large to grow into are rejected rather than wrapping.
+    universe.load_plaintext("OO\nOO").unwrap();
+    assert_eq!(
+        universe.load_rle("x = 70000, y = 70000, rule = B36/S23\n!"),
+        Err(UniverseError::InvalidSize {
+            width: 70000,
+            height: 70000
+        })
+    );
+    assert_eq!((universe.width(), universe.height()), (4, 4));
+    assert_eq!(universe.population(), 4);
+    assert_eq!(universe.rule_string(), "B3/S23");
+}
+
+#[test]
+fn patterns_wrap_on_tiny_universes() {
+    let mut universe = Universe::with_size(3, 2, InitType::Clear).unwrap();
+    universe.put_line();
+    assert_eq!(universe.to_plaintext(), "OOO\nOOO\n");
+    // Every cell sees the whole board, so all eight neighbors are alive.
+    universe.tick();
+    assert_eq!(universe.to_plaintext(), "...\n...\n");
+
+    let mut universe = Universe::with_size(3, 2, InitType::Clear).unwrap();
+    universe.put_spaceship();
+    assert_eq!(universe.to_plaintext(), "OOO\nO.O\n");
+    universe.tick();
+    assert_eq!(universe.to_plaintext(), "...\n...\n");
+}
@@ -11,3 +11,14 @@ wasm_bindgen_test_configure!(run_in_browser);
 fn pass() {
     assert_eq!(1 + 1, 2);
 }
+
+#[wasm_bindgen_test]
+fn blinker_oscillates_in_the_browser() {
+    let mut universe = wasm_game_of_life::Universe::new(wasm_game_of_life::InitType::Clear);
+    universe.load_plaintext("OOO").unwrap();
+    let start = universe.to_plaintext();
+    universe.tick();
+    assert_ne!(universe.to_plaintext(), start);
+    universe.tick();
+    assert_eq!(universe.to_plaintext(), start);
+}
@@ -46,6 +46,17 @@ max of last 100 = ${Math.round(max)}
 };
 
 const universe = Universe.new(1);
+universe.set_stats_capacity(100);
+
+// `?perf` logs how long each tick takes; `?scalar` switches a build with the
+// `simd` feature to the plain 64-bit backend, to compare the two.
+const params = new URLSearchParams(window.location.search);
+if (params.has("perf")) {
+    universe.set_perf();
+}
+if (params.has("scalar") && universe.set_simd) {
+    universe.set_simd(false);
+}
 const width = universe.width();
 const height = universe.height();
 
@@ -81,35 +92,91 @@ const bitIsSet = (n, arr) => {
     return (arr[byte] & mask) === mask;
 };
 
-const drawCells = () => {
+const drawCell = (idx, cells) => {
+    const row = Math.floor(idx / width);
+    const col = idx % width;
+    ctx.fillStyle = bitIsSet(idx, cells)
+        ? ALIVE_COLOR
+        : DEAD_COLOR;
+    ctx.fillRect(
+        col * (CELL_SIZE + 1) + 1,
+        row * (CELL_SIZE + 1) + 1,
+        CELL_SIZE,
+        CELL_SIZE
+    );
+};
+
+const cellsArray = () => {
     const cellsPtr = universe.cells();
-    const cells = new Uint8Array(memory.buffer, cellsPtr, width * height / 8);
+    return new Uint8Array(memory.buffer, cellsPtr, Math.ceil(width * height / 8));
+};
+
+const drawCells = () => {
+    const cells = cellsArray();
 
     ctx.beginPath();
     for (let row = 0; row < height; row++) {
         for (let col = 0; col < width; col++) {
-            const idx = getIndex(row, col);
-            ctx.fillStyle = bitIsSet(idx, cells)
-                ? ALIVE_COLOR
-                : DEAD_COLOR;
-            ctx.fillRect(
-                col * (CELL_SIZE + 1) + 1,
-                row * (CELL_SIZE + 1) + 1,
-                CELL_SIZE,
-                CELL_SIZE
-            );
+            drawCell(getIndex(row, col), cells);
         }
     }
     ctx.stroke();
 };
 
+// Repaints only the cells that flipped in the last tick.
+const drawChangedCells = () => {
+    const cells = cellsArray();
+    const changed = new Uint32Array(
+        memory.buffer,
+        universe.changed_cells(),
+        universe.changed_cells_len()
+    );
+
+    ctx.beginPath();
+    for (let i = 0; i < changed.length; i++) {
+        drawCell(changed[i], cells);
+    }
+    ctx.stroke();
+};
+
+const generationStats = document.getElementById("generation-stats");
+
+const renderGenerationStats = () => {
+    const population = universe.stats_population();
+    const n = population.length;
+    if (n === 0) {
+        return;
+    }
+    const births = universe.stats_births();
+    const deaths = universe.stats_deaths();
+    const density = universe.stats_density();
+    const boxes = universe.stats_bounding_boxes();
+
+    let sumBirths = 0;
+    let sumDeaths = 0;
+    for (let i = 0; i < n; i++) {
+        sumBirths += births[i];
+        sumDeaths += deaths[i];
+    }
+    const [col, row, w, h] = boxes.subarray(4 * (n - 1));
+
+    generationStats.textContent = `
+Generation ${universe.generation()}:
+      population = ${population[n - 1]}
+         density = ${(density[n - 1] * 100).toFixed(1)} %
+    bounding box = ${w}x${h} at (${col}, ${row})
+births, last ${n} = ${sumBirths}
+deaths, last ${n} = ${sumDeaths}
+`.trim();
+};
+
 let animationId = null;
 const renderLoop = () => {
     fps.render();
     universe.tick();
 
-    drawGrid();
-    drawCells();
+    drawChangedCells();
+    renderGenerationStats();
 
     animationId = requestAnimationFrame(renderLoop);
 };
@@ -144,12 +211,48 @@ playOnceButton.addEventListener("click", event => {
     if (isPaused()) {
         universe.tick();
 
-        drawGrid();
-        drawCells();
+        drawChangedCells();
         fps.render();
+        renderGenerationStats();
     }
 });
 
+canvas.addEventListener("click", event => {
+    const boundingRect = canvas.getBoundingClientRect();
+
+    const scaleX = canvas.width / boundingRect.width;
+    const scaleY = canvas.height / boundingRect.height;
+
+    const canvasLeft = (event.clientX - boundingRect.left) * scaleX;
+    const canvasTop = (event.clientY - boundingRect.top) * scaleY;
+
+    const row = Math.min(Math.floor(canvasTop / (CELL_SIZE + 1)), height - 1);
+    const col = Math.min(Math.floor(canvasLeft / (CELL_SIZE + 1)), width - 1);
+
+    universe.toggle_cell(row, col);
+
+    ctx.beginPath();
+    drawCell(getIndex(row, col), cellsArray());
+    ctx.stroke();
+});
+
+const fastForwardButton = document.getElementById("fast-forward");
+const stepStats = document.getElementById("step-stats");
+
+fastForwardButton.addEventListener("click", event => {
+    const stats = universe.step(1000);
+    stepStats.textContent = `
+population = ${stats.population}
+    births = ${stats.births}
+    deaths = ${stats.deaths}
+   elapsed = ${stats.elapsed_ms.toFixed(1)} ms
+`.trim();
+    stats.free();
+
+    drawCells();
+    renderGenerationStats();
+});
+
 // universe.put_spaceship();
 universe.put_line();
 